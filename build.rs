@@ -0,0 +1,41 @@
+use std::env;
+use std::process::Command;
+
+// Records the features cargo resolved for the polars dependency, default ones
+// included, as POLARS_FEATURES for `sysinfo`. Empty when cargo metadata fails.
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+
+    let features = polars_features().unwrap_or_default();
+    println!("cargo:rustc-env=POLARS_FEATURES={}", features.join(","));
+}
+
+fn polars_features() -> Option<Vec<String>> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    // The dependencies are already on disk when build scripts run
+    let output = Command::new(cargo)
+        .args(["metadata", "--format-version", "1", "--offline"])
+        .current_dir(env::var("CARGO_MANIFEST_DIR").ok()?)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let polars = metadata["resolve"]["nodes"]
+        .as_array()?
+        .iter()
+        .find(|node| node["id"].as_str().is_some_and(|id| id.contains("#polars@")))?;
+
+    // Without "default" and the optional polars-* crates the features turn on
+    let features = polars["features"]
+        .as_array()?
+        .iter()
+        .filter_map(|feature| feature.as_str())
+        .filter(|feature| *feature != "default" && !feature.starts_with("polars-"))
+        .map(str::to_string)
+        .collect();
+    Some(features)
+}
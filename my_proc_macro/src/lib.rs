@@ -9,59 +9,711 @@ use syn::spanned::Spanned;
 use syn::ItemFn;
 use syn::File;
 
+// Arguments of #[print_source(title = "...", url = "...", notes = NOTES_EX005, show_result = true)]
+#[derive(Default)]
+struct SourceArgs {
+    title: Option<syn::LitStr>,
+    url: Option<syn::LitStr>,
+    notes: Option<syn::Expr>, // a &'static str, usually a NOTES_EXnnn const
+    show_result: bool,
+}
+
+impl SourceArgs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("title") {
+            self.title = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("url") {
+            self.url = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("notes") {
+            self.notes = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("show_result") {
+            // `show_result` alone means `show_result = true`
+            self.show_result = match meta.value() {
+                Ok(value) => value.parse::<syn::LitBool>()?.value,
+                Err(_) => true,
+            };
+        } else {
+            return Err(meta.error("unsupported print_source argument, expected title, url, notes or show_result"));
+        }
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.title.is_none() && self.url.is_none() && self.notes.is_none() && !self.show_result
+    }
+}
+
 #[proc_macro_attribute]
-pub fn print_source(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn print_source(attr: TokenStream, item: TokenStream) -> TokenStream {
+
+    let mut args = SourceArgs::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attr with parser);
 
-    let input = TokenStream2::from(item.clone());
+    let original_code = source_text(&TokenStream2::from(item.clone()));
     let mut input_fn = parse_macro_input!(item as ItemFn);
-    
-    let fn_name = &input_fn.sig.ident;
-    let wrapper_name = syn::Ident::new(&format!("{}_wrapper", fn_name), fn_name.span());
-    
-    // Parse the input as a complete Rust file
-    let file: syn::File = syn::parse2(input.clone()).unwrap();
 
-    // Use prettyplease to format the code while preserving original formatting
-    let original_code = prettyplease::unparse(&file);
+    // Attributes below this one see the function it returns, in which the source is no longer
+    // the one written, so #[example] would register it without its source
+    if let Some(attr) = input_fn.attrs.iter().find(|attr| attr.path().segments.last().is_some_and(|segment| segment.ident == "example")) {
+        return syn::Error::new(attr.span(), "put #[example] before #[print_source], so that it registers the source")
+            .to_compile_error()
+            .into();
+    }
+
+    if let Err(error) = check_supported(&input_fn, &args) {
+        return error.to_compile_error().into();
+    }
+
+    let fn_name = &input_fn.sig.ident;
 
     let return_type = &input_fn.sig.output;
-    
-    let original_body = input_fn.block;
-    input_fn.block = parse_quote!({
-        //println!("Function source code:\n\n{}", #original_code);
-        println!("\n{}", #original_code);
-        #original_body
-    });
-    
-    // Get the function arguments
-    let args = input_fn.sig.inputs.iter().collect::<Vec<_>>();
-    let arg_names = args.iter().map(|arg| {
-        if let syn::FnArg::Typed(pat_type) = arg {
-            if let syn::Pat::Ident(pat_ident) = &*pat_type.pat {
-                &pat_ident.ident
+    let result_type = match return_type {
+        syn::ReturnType::Default => quote! { () },
+        syn::ReturnType::Type(_, ty) => quote! { #ty },
+    };
+
+    // With arguments: a header naming the function, like print_function!(), then its title and docs link
+    let header = if args.is_empty() {
+        quote! {}
+    } else {
+        let name = fn_name.to_string();
+        let title = args.title.iter();
+        let url = args.url.iter();
+        quote! {
+            println!("FUNCTION  : {}::{}", module_path!(), #name);
+            #( println!("TITLE     : {}", #title); )*
+            #( println!("DOCS      : {}", #url); )*
+        }
+    };
+
+    let show_result = if args.show_result {
+        quote! {
+            crate::utilities::PrintResult::print_result(&result);
+        }
+    } else {
+        quote! {}
+    };
+    let notes = args.notes.iter();
+    let print_notes = quote! { #( println!("\nNOTES\n{}", #notes); )* };
+
+    let original_body = input_fn.block.clone();
+    input_fn.block = if args.show_result || args.notes.is_some() {
+        // Keep the result to print it, and the notes, after the body has run
+        parse_quote!({
+            if crate::utilities::printing() {
+                #header
+                println!("\n{}", crate::highlight::rust(#original_code));
+            }
+            let result: #result_type = (|| -> #result_type #original_body)();
+            if crate::utilities::printing() {
+                #show_result
+                #print_notes
+            }
+            result
+        })
+    } else {
+        parse_quote!({
+            if crate::utilities::printing() {
+                #header
+                println!("\n{}", crate::highlight::rust(#original_code));
+            }
+            #original_body
+        })
+    };
+
+    // Only the function itself is emitted, no item next to it, so that it also
+    // works on methods of trait impls. #[example] registers the source, see example_meta.
+    quote! { #input_fn }.into()
+}
+
+// The function as written, comments included. When the span does not map to
+// a source file, eg for code generated by another macro, fall back to prettyplease,
+// which drops the comments.
+fn source_text(item: &TokenStream2) -> String {
+    verbatim_source(item).unwrap_or_else(|| match syn::parse2::<syn::File>(item.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => item.to_string(),
+    })
+}
+
+// Functions print_source cannot instrument, reported at the offending tokens
+fn check_supported(input_fn: &ItemFn, args: &SourceArgs) -> syn::Result<()> {
+    let sig = &input_fn.sig;
+    if let Some(constness) = &sig.constness {
+        return Err(syn::Error::new(constness.span(), "print_source cannot print from a const fn"));
+    }
+
+    // The result is kept by running the body in a closure, see print_source
+    if args.show_result || args.notes.is_some() {
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(syn::Error::new(asyncness.span(), "show_result and notes are not supported on async functions"));
+        }
+        if let syn::ReturnType::Type(_, ty) = &sig.output {
+            if contains_impl_trait(&quote! { #ty }) {
+                return Err(syn::Error::new(ty.span(), "show_result and notes need a return type that can be named, not impl Trait"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn contains_impl_trait(tokens: &TokenStream2) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == "impl",
+        proc_macro2::TokenTree::Group(group) => contains_impl_trait(&group.stream()),
+        _ => false,
+    })
+}
+
+fn option_tokens<T: quote::ToTokens>(value: Option<&T>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+// Text of the annotated item in its source file, from its first to its last token.
+// Lines after the first lose the indentation of the first one, so a method reads
+// like a free function.
+fn verbatim_source(item: &TokenStream2) -> Option<String> {
+    let mut tokens = TokenStream::from(item.clone()).into_iter();
+    let first = tokens.next()?.span();
+    let last = tokens.last().map_or(first, |token| token.span());
+
+    let text = std::fs::read_to_string(first.local_file()?).ok()?;
+    let lines: Vec<&str> = text.lines().collect();
+
+    // Lines and columns are one-based, the end span points just after the last token
+    let (start_line, start_column) = (first.line(), first.column());
+    let end = last.end();
+    let (end_line, end_column) = (end.line(), end.column());
+    if start_line == 0 || start_column == 0 || end_line < start_line || end_line > lines.len() {
+        return None;
+    }
+
+    let mut source = String::new();
+    for (line_number, line) in (start_line..=end_line).zip(&lines[start_line - 1..end_line]) {
+        let chars: Vec<char> = line.chars().collect();
+        let from = if line_number == start_line {
+            start_column - 1
+        } else {
+            chars
+                .iter()
+                .take(start_column - 1)
+                .take_while(|c| c.is_whitespace())
+                .count()
+        };
+        let to = if line_number == end_line { end_column - 1 } else { chars.len() };
+        if from > to || to > chars.len() {
+            return None;
+        }
+        source.extend(&chars[from..to]);
+        source.push('\n');
+    }
+    Some(source)
+}
+
+
+
+// // 1. https://www.perplexity.ai/search/in-rust-given-this-code-in-mai-gj9jHnJTSTiuKjRdnJC.VQ
+// // 2. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-GlM9AqLcSiOjnQa3xdX.cw
+// // 3. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-dSslGmFmQhuDqK5gyDAS6A
+// // 4. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-cGA.G98iRr2Ro1vkseKMeQ
+// // 5. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-F3ZhQwB7TUeLTsDN1Va5xg
+//--------------------
+
+// Arguments of #[example(chapter = 14, id = 5, title = "...", depends_on = "ex001")]
+#[derive(Default)]
+struct ExampleArgs {
+    chapter: Option<syn::LitInt>,
+    id: Option<syn::LitInt>,
+    title: Option<syn::LitStr>,
+    depends_on: Vec<syn::LitStr>, // examples producing the arguments, in order
+    expect_error: bool,
+    snapshot: bool, // generate a test comparing the output to snapshots/<chapter>/<example>.snap
+}
+
+impl ExampleArgs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("chapter") {
+            self.chapter = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("id") {
+            self.id = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("title") {
+            self.title = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("depends_on") {
+            // depends_on = "ex001" or depends_on = ["ex001", "ex002"]
+            let value = meta.value()?;
+            if value.peek(syn::token::Bracket) {
+                let content;
+                syn::bracketed!(content in value);
+                let names = content.parse_terminated(<syn::LitStr as syn::parse::Parse>::parse, syn::Token![,])?;
+                self.depends_on = names.into_iter().collect();
             } else {
-                panic!("Unsupported argument pattern")
+                self.depends_on = vec![value.parse()?];
             }
+        } else if meta.path.is_ident("expect_error") {
+            // `expect_error` alone means `expect_error = true`
+            self.expect_error = match meta.value() {
+                Ok(value) => value.parse::<syn::LitBool>()?.value,
+                Err(_) => true,
+            };
+        } else if meta.path.is_ident("snapshot") {
+            self.snapshot = match meta.value() {
+                Ok(value) => value.parse::<syn::LitBool>()?.value,
+                Err(_) => true,
+            };
         } else {
-            panic!("Unsupported argument type")
+            return Err(meta.error("unsupported example argument, expected chapter, id, title, depends_on, expect_error or snapshot"));
         }
-    });
-    
+        Ok(())
+    }
+}
+
+// Register the function as an example of a chapter, so the runner finds it
+// without a hand-written list. Each argument of the function receives the
+// output of the matching `depends_on` example, eg a `&DataFrame`.
+// Put it before #[print_source] so that the source is registered as well.
+#[proc_macro_attribute]
+pub fn example(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = ExampleArgs::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attr with parser);
+
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let fn_name = &input_fn.sig.ident;
+
+    let (Some(chapter), Some(id)) = (&args.chapter, &args.id) else {
+        return syn::Error::new(proc_macro2::Span::call_site(), "example needs a chapter and an id, eg #[example(chapter = 14, id = 5)]")
+            .to_compile_error()
+            .into();
+    };
+
+    // Examples are looked up and depended on by name, exNNN for id NNN
+    let expected_name = match id.base10_parse::<u32>() {
+        Ok(number) => format!("ex{:03}", number),
+        Err(error) => return error.to_compile_error().into(),
+    };
+    if fn_name != &expected_name {
+        let message = format!("id = {} is the example {}, but the function is named {}", id, expected_name, fn_name);
+        return syn::Error::new(id.span(), message).to_compile_error().into();
+    }
+
+    // The registered runner calls the function by name with the inputs as arguments
+    if let Some(receiver) = input_fn.sig.receiver() {
+        return syn::Error::new(receiver.span(), "an example takes its inputs as arguments, it cannot be a method")
+            .to_compile_error()
+            .into();
+    }
+    if !input_fn.sig.generics.params.is_empty() {
+        return syn::Error::new(input_fn.sig.generics.span(), "an example cannot be generic, the runner has no types to call it with")
+            .to_compile_error()
+            .into();
+    }
+
+    let inputs = input_fn.sig.inputs.len();
+    if inputs != args.depends_on.len() {
+        let message = format!(
+            "{} takes {} argument(s) but depends_on lists {} example(s)",
+            fn_name,
+            inputs,
+            args.depends_on.len(),
+        );
+        return syn::Error::new(input_fn.sig.inputs.span(), message).to_compile_error().into();
+    }
+
+    if args.snapshot && args.expect_error {
+        return syn::Error::new(proc_macro2::Span::call_site(), "an example expected to fail has no output to snapshot")
+            .to_compile_error()
+            .into();
+    }
+
+    let name = fn_name.to_string();
+    let title = args.title.map_or_else(String::new, |title| title.value());
+    let depends_on = &args.depends_on;
+    let expect_error = args.expect_error;
+    let indexes = 0..inputs;
+
+    let meta = match example_meta(&input_fn) {
+        Ok(meta) => meta,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    // A test per example, see crate::snapshot
+    let snapshot_test = if args.snapshot {
+        let test_name = syn::Ident::new(&format!("{}_snapshot", fn_name), fn_name.span());
+        quote! {
+            #[cfg(test)]
+            #[test]
+            fn #test_name() {
+                crate::snapshot::check(#chapter, #name);
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let output = quote! {
         #input_fn
 
-        pub fn #wrapper_name(#(#args),*) #return_type {
-            #fn_name(#(#arg_names),*)
+        ::inventory::submit! {
+            crate::registry::Example {
+                chapter: #chapter,
+                id: #id,
+                name: #name,
+                title: #title,
+                depends_on: &[#(#depends_on),*],
+                runner: {
+                    fn runner(inputs: &[crate::registry::Output]) -> ::polars::prelude::PolarsResult<crate::registry::Output> {
+                        #fn_name(#(crate::registry::FromOutput::from_output(&inputs[#indexes])?),*).map(Into::into)
+                    }
+                    runner
+                },
+                expect_error: #expect_error,
+                meta: #meta,
+            }
         }
+
+        #snapshot_test
     };
-    
+
     output.into()
 }
 
+// `Some(fn)` returning the source and the #[print_source] arguments of the function, or `None`
+// without #[print_source]. The source is the function as #[print_source] receives it: the
+// tokens after its attribute.
+fn example_meta(input_fn: &ItemFn) -> syn::Result<TokenStream2> {
+    let Some(position) = input_fn.attrs.iter().position(is_print_source) else {
+        return Ok(quote! { None });
+    };
+
+    let mut args = SourceArgs::default();
+    if let syn::Meta::List(_) = &input_fn.attrs[position].meta {
+        input_fn.attrs[position].parse_nested_meta(|meta| args.parse(meta))?;
+    }
 
+    let mut source_fn = input_fn.clone();
+    source_fn.attrs.drain(..=position);
+    let source = source_text(&quote! { #source_fn });
 
-// // 1. https://www.perplexity.ai/search/in-rust-given-this-code-in-mai-gj9jHnJTSTiuKjRdnJC.VQ
-// // 2. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-GlM9AqLcSiOjnQa3xdX.cw
-// // 3. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-dSslGmFmQhuDqK5gyDAS6A
-// // 4. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-cGA.G98iRr2Ro1vkseKMeQ
-// // 5. https://www.perplexity.ai/search/1-in-rust-polars-i-have-this-m-F3ZhQwB7TUeLTsDN1Va5xg
\ No newline at end of file
+    let title = option_tokens(args.title.as_ref());
+    let url = option_tokens(args.url.as_ref());
+    let notes = option_tokens(args.notes.as_ref());
+    Ok(quote! {
+        Some({
+            fn meta() -> crate::registry::ExampleMeta {
+                crate::registry::ExampleMeta {
+                    title: #title,
+                    url: #url,
+                    notes: #notes,
+                    source: #source,
+                }
+            }
+            meta
+        })
+    })
+}
+
+fn is_print_source(attr: &syn::Attribute) -> bool {
+    attr.path().segments.last().is_some_and(|segment| segment.ident == "print_source")
+}
+
+//--------------------
+
+// A field of a struct deriving IntoDataFrame or FromDataFrame, and the column it maps to.
+// The column is named after the field unless renamed with #[polars(rename = "avg M birthday")].
+struct ColumnField {
+    ident: syn::Ident,
+    ty: syn::Type,
+    column: String,
+}
+
+fn column_fields(input: &syn::DeriveInput) -> syn::Result<Vec<ColumnField>> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(fields) => &fields.named,
+            other => return Err(syn::Error::new(other.span(), "expected a struct with named fields, the names are used for the columns")),
+        },
+        _ => return Err(syn::Error::new(input.ident.span(), "expected a struct with named fields, one per column")),
+    };
+
+    let mut columns = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().expect("named field");
+        let mut column = ident.to_string();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("polars")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    column = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported polars argument, expected rename"))
+                }
+            })?;
+        }
+        columns.push(ColumnField { ident, ty: field.ty.clone(), column });
+    }
+    Ok(columns)
+}
+
+// Build a DataFrame from a slice of structs, with crate::convert::to_dataframe(&rows).
+// Each field type implements crate::convert::ToAnyValue. The struct implements it too,
+// so it can be the field of another struct, as a Struct column.
+#[proc_macro_derive(IntoDataFrame, attributes(polars))]
+pub fn into_dataframe(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    let fields = match column_fields(&input) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let columns: Vec<_> = fields.iter().map(|field| &field.column).collect();
+
+    let output = quote! {
+        impl #impl_generics crate::convert::IntoDataFrame for #name #ty_generics #where_clause {
+            fn into_dataframe(rows: &[Self]) -> ::polars::prelude::PolarsResult<::polars::prelude::DataFrame> {
+                ::polars::prelude::DataFrame::new(vec![
+                    #( crate::convert::column::<#types>(#columns, rows.iter().map(|row| &row.#idents))?, )*
+                ])
+            }
+        }
+
+        impl #impl_generics crate::convert::ToAnyValue for #name #ty_generics #where_clause {
+            fn dtype() -> ::polars::prelude::DataType {
+                ::polars::prelude::DataType::Struct(vec![
+                    #( ::polars::prelude::Field::new(#columns, <#types as crate::convert::ToAnyValue>::dtype()), )*
+                ])
+            }
+
+            fn to_any_value(&self) -> ::polars::prelude::PolarsResult<::polars::prelude::AnyValue<'static>> {
+                let ::polars::prelude::DataType::Struct(fields) = <Self as crate::convert::ToAnyValue>::dtype() else {
+                    unreachable!()
+                };
+                let values = vec![
+                    #( crate::convert::ToAnyValue::to_any_value(&self.#idents)?, )*
+                ];
+                Ok(::polars::prelude::AnyValue::StructOwned(Box::new((values, fields))))
+            }
+        }
+    };
+
+    output.into()
+}
+
+// Read the rows of a DataFrame into a Vec of structs, with crate::convert::from_dataframe(&df).
+// Each field type implements crate::convert::FromAnyValue. The struct implements it too,
+// so it can be read from a Struct column.
+#[proc_macro_derive(FromDataFrame, attributes(polars))]
+pub fn from_dataframe(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    let fields = match column_fields(&input) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let name = &input.ident;
+    let target = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let idents: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let columns: Vec<_> = fields.iter().map(|field| &field.column).collect();
+    let indices = (0..fields.len()).map(proc_macro2::Literal::usize_unsuffixed);
+    // The field types as written, for the error messages, eg "Option<String>"
+    let expected = types.iter().map(|ty| {
+        quote! { #ty }
+            .to_string()
+            .replace(" < ", "<")
+            .replace(" >", ">")
+            .replace(" , ", ", ")
+    });
+
+    let output = quote! {
+        impl #impl_generics crate::convert::FromDataFrame for #name #ty_generics #where_clause {
+            fn from_dataframe(df: &::polars::prelude::DataFrame) -> ::polars::prelude::PolarsResult<Vec<Self>> {
+                let columns = crate::convert::columns(df, #target, &[
+                    #(
+                        crate::convert::ColumnCheck {
+                            name: #columns,
+                            accepts: <#types as crate::convert::FromAnyValue>::accepts,
+                            expected: #expected,
+                        },
+                    )*
+                ])?;
+                (0..df.height())
+                    .map(|row| Ok(Self {
+                        #( #idents: crate::convert::cell(columns[#indices], row)?, )*
+                    }))
+                    .collect()
+            }
+        }
+
+        impl #impl_generics crate::convert::FromAnyValue for #name #ty_generics #where_clause {
+            fn accepts(dtype: &::polars::prelude::DataType) -> bool {
+                match dtype {
+                    ::polars::prelude::DataType::Struct(fields) => true #(
+                        && fields.iter().any(|field| {
+                            field.name().as_str() == #columns
+                                && <#types as crate::convert::FromAnyValue>::accepts(field.data_type())
+                        })
+                    )*,
+                    _ => false,
+                }
+            }
+
+            fn from_any_value(value: ::polars::prelude::AnyValue) -> ::polars::prelude::PolarsResult<Self> {
+                let fields = crate::convert::struct_fields(value)?;
+                Ok(Self {
+                    #( #idents: crate::convert::struct_field(&fields, #columns)?, )*
+                })
+            }
+        }
+    };
+
+    output.into()
+}
+
+// A Polars Enum dtype from a Rust enum of unit variants, see crate::convert::PolarsEnum.
+// The categories are the variant names, unless renamed with #[polars(rename = "Jan")],
+// and sort in the order of the variants, unless the enum has #[polars(ordering = "lexical")].
+#[proc_macro_derive(PolarsEnum, attributes(polars))]
+pub fn polars_enum(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::DeriveInput);
+    match polars_enum_impl(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn polars_enum_impl(input: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(input.ident.span(), "PolarsEnum can only be derived for an enum"));
+    };
+
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(input.ident.span(), "a Polars Enum needs at least one variant"));
+    }
+
+    let mut ordering = quote! { Physical };
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("polars")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ordering") {
+                let value = meta.value()?.parse::<syn::LitStr>()?;
+                ordering = match value.value().as_str() {
+                    "physical" => quote! { Physical },
+                    "lexical" => quote! { Lexical },
+                    _ => return Err(syn::Error::new(value.span(), "expected \"physical\" or \"lexical\"")),
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unsupported polars argument, expected ordering"))
+            }
+        })?;
+    }
+
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new(variant.fields.span(), "a Polars Enum category cannot hold data, expected a unit variant"));
+        }
+        let mut name = variant.ident.to_string();
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("polars")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported polars argument, expected rename"))
+                }
+            })?;
+        }
+        variants.push(&variant.ident);
+        names.push(name);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics crate::convert::PolarsEnum for #name #ty_generics #where_clause {
+            const NAMES: &'static [&'static str] = &[#( #names ),*];
+            const ORDERING: ::polars::prelude::CategoricalOrdering = ::polars::prelude::CategoricalOrdering::#ordering;
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #( Self::#variants => #names, )*
+                }
+            }
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #( #names => Some(Self::#variants), )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl #impl_generics crate::convert::ToAnyValue for #name #ty_generics #where_clause {
+            fn dtype() -> ::polars::prelude::DataType {
+                <Self as crate::convert::PolarsEnum>::enum_dtype()
+            }
+
+            fn to_any_value(&self) -> ::polars::prelude::PolarsResult<::polars::prelude::AnyValue<'static>> {
+                let name = <Self as crate::convert::PolarsEnum>::name(self);
+                Ok(::polars::prelude::AnyValue::StringOwned(name.into()))
+            }
+        }
+
+        impl #impl_generics crate::convert::FromAnyValue for #name #ty_generics #where_clause {
+            fn accepts(dtype: &::polars::prelude::DataType) -> bool {
+                crate::convert::enum_accepts(dtype)
+            }
+
+            fn from_any_value(value: ::polars::prelude::AnyValue) -> ::polars::prelude::PolarsResult<Self> {
+                crate::convert::enum_from_any_value(value)
+            }
+        }
+    })
+}
+
+//--------------------
+
+// Print the unoptimized and optimized plans of every LazyFrame collected in the
+// function, see crate::explain. Calls inside macros, eg println!("{}", lf.collect()?),
+// are left as they are.
+#[proc_macro_attribute]
+pub fn explain(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(TokenStream2::from(attr).span(), "explain takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut input_fn = parse_macro_input!(item as ItemFn);
+    syn::visit_mut::VisitMut::visit_block_mut(&mut ExplainCollect, &mut input_fn.block);
+    input_fn.block.stmts.insert(0, parse_quote! {
+        use crate::explain::{ExplainIterator as _, ExplainLazyFrame as _};
+    });
+
+    quote! { #input_fn }.into()
+}
+
+// Renames .collect() to .explained_collect(), keeping any turbofish. Every method call
+// named collect is renamed, so a collect of another type than LazyFrame or Iterator
+// fails to resolve, see tests/compile_fail/explain_other_collect.rs.
+struct ExplainCollect;
+
+impl syn::visit_mut::VisitMut for ExplainCollect {
+    fn visit_expr_method_call_mut(&mut self, call: &mut syn::ExprMethodCall) {
+        syn::visit_mut::visit_expr_method_call_mut(self, call);
+        if call.method == "collect" && call.args.is_empty() {
+            call.method = syn::Ident::new("explained_collect", call.method.span());
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use polars::prelude::*;
+
+use crate::registry::{Chapter, Output};
+use crate::runner::{self, ExampleReport, Status};
+use crate::utilities::*;
+
+// Wall time of one example over the measured runs
+#[derive(Debug)]
+pub struct BenchResult {
+    pub chapter: &'static str,
+    pub example: &'static str,
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub rows: Option<usize>, // height of the returned DataFrame or length of the Series
+}
+
+// Time the selected examples of a chapter: one warmup run, then `runs` measured
+// runs, all with stdout silenced. The examples they depend on run once, to
+// produce their inputs, and are not measured.
+pub fn bench_chapter(
+    chapter: &Chapter,
+    ids: Option<&[u32]>,
+    runs: usize,
+    reports: &mut Vec<ExampleReport>,
+    results: &mut Vec<BenchResult>,
+) {
+    println!("{:03} {} benchmarks", chapter.id, chapter.title);
+    println!("{}", chapter.url);
+    println!("Timing each example over {} runs after a warmup.\n", runs);
+
+    let selected = runner::selected_examples(chapter, ids);
+    let needed = runner::with_dependencies(chapter, &selected);
+    let mut outputs: HashMap<&'static str, Output> = HashMap::new();
+
+    for example in chapter.examples.iter().filter(|ex| needed.contains(ex.name)) {
+        // The warmup also stores the output used as input by later examples
+        let mut report = quietly(|| runner::execute(chapter, example, &mut outputs));
+        if report.status.is_failure() {
+            runner::print_failure(&report);
+        }
+        if report.status != Status::Ok || !selected.contains(example.name) {
+            reports.push(report);
+            continue;
+        }
+
+        // Nothing is formatted for output while timing, see utilities::printing
+        let mut timings = Vec::with_capacity(runs);
+        set_printing(false);
+        let failure = quietly(|| {
+            for _ in 0..runs {
+                let start = Instant::now();
+                let result = runner::run_example(example, &outputs);
+                timings.push(start.elapsed());
+                if let Err(e) = result {
+                    return Some(e);
+                }
+            }
+            None
+        });
+        set_printing(true);
+
+        // Eg an example writing a file it cannot overwrite on the second run
+        if let Some(error) = failure {
+            report.status = Status::Failed;
+            report.error = Some(error);
+            runner::print_failure(&report);
+            reports.push(report);
+            continue;
+        }
+
+        let (min, median, p95) = statistics(&mut timings);
+        report.elapsed = median;
+        reports.push(report);
+
+        results.push(BenchResult {
+            chapter: chapter.name,
+            example: example.name,
+            runs,
+            min,
+            median,
+            p95,
+            rows: match outputs.get(example.name) {
+                Some(Output::Frame(df)) => Some(df.height()),
+                Some(Output::Series(s)) => Some(s.len()),
+                _ => None,
+            },
+        });
+    }
+}
+
+// Minimum, median and 95th percentile (nearest rank)
+fn statistics(timings: &mut [Duration]) -> (Duration, Duration, Duration) {
+    timings.sort_unstable();
+    let n = timings.len();
+    let median = if n % 2 == 1 {
+        timings[n / 2]
+    } else {
+        (timings[n / 2 - 1] + timings[n / 2]) / 2
+    };
+    let p95 = timings[(n * 95).div_ceil(100) - 1];
+    (timings[0], median, p95)
+}
+
+//--------------------
+
+pub fn print_results(results: &[BenchResult]) {
+    let chapter_width = results.iter().map(|r| r.chapter.len()).max().unwrap_or(0).max("CHAPTER".len());
+
+    hr1();
+    println!("BENCHMARK (polars {})", polars::VERSION);
+    println!(
+        "{:<chapter_width$}  {:<7}  {:>5}  {:>10}  {:>10}  {:>10}  {:>8}",
+        "CHAPTER", "EXAMPLE", "RUNS", "MIN", "MEDIAN", "P95", "ROWS",
+    );
+    hr3();
+    for result in results {
+        println!(
+            "{:<chapter_width$}  {:<7}  {:>5}  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8}",
+            result.chapter,
+            result.example,
+            result.runs,
+            millis(result.min),
+            millis(result.median),
+            millis(result.p95),
+            result.rows.map(|rows| rows.to_string()).unwrap_or_else(|| "-".to_string()),
+        );
+    }
+    hr3();
+}
+
+// Save the results as JSON when the file name ends in .json, as CSV otherwise.
+// The Polars version is recorded to compare runs across upgrades.
+pub fn write_results(results: &[BenchResult], path: &Path) -> PolarsResult<()> {
+    let mut df = df!(
+        "polars" => vec![polars::VERSION; results.len()],
+        "chapter" => results.iter().map(|r| r.chapter).collect::<Vec<_>>(),
+        "example" => results.iter().map(|r| r.example).collect::<Vec<_>>(),
+        "runs" => results.iter().map(|r| r.runs as u64).collect::<Vec<_>>(),
+        "min_ms" => results.iter().map(|r| millis(r.min)).collect::<Vec<_>>(),
+        "median_ms" => results.iter().map(|r| millis(r.median)).collect::<Vec<_>>(),
+        "p95_ms" => results.iter().map(|r| millis(r.p95)).collect::<Vec<_>>(),
+        "rows" => results.iter().map(|r| r.rows.map(|rows| rows as u64)).collect::<Vec<_>>(),
+    )?;
+
+    let mut file = File::create(path)?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let rows = crate::format::rows(&df)?;
+        serde_json::to_writer_pretty(&mut file, &rows).map_err(|e| PolarsError::ComputeError(e.to_string().into()))?;
+        writeln!(file)?;
+    } else {
+        CsvWriter::new(&mut file).finish(&mut df)?;
+    }
+    Ok(())
+}
+
+// Milliseconds, rounded to the microsecond
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
@@ -0,0 +1,517 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use polars::prelude::*;
+use polars_arrow::array::Utf8ViewArray;
+
+// Conversions between Rust structs and DataFrames, for records too long to
+// write as the parallel arrays of df!(), and for results handed to plain Rust code.
+// The impls for structs are generated by #[derive(IntoDataFrame)] and
+// #[derive(FromDataFrame)] in my_proc_macro:
+//
+//     #[derive(IntoDataFrame, FromDataFrame)]
+//     struct Rating {
+//         #[polars(rename = "Movie")]
+//         movie: &'static str,
+//         count: Option<i32>,
+//     }
+//     let df = to_dataframe(&ratings)?;
+//     let ratings: Vec<Rating> = from_dataframe(&df)?;
+
+// A struct whose values are the rows of a DataFrame, one column per field
+pub trait IntoDataFrame: Sized {
+    fn into_dataframe(rows: &[Self]) -> PolarsResult<DataFrame>;
+}
+
+// The DataFrame of a Vec or slice of structs deriving IntoDataFrame
+pub fn to_dataframe<T: IntoDataFrame>(rows: &[T]) -> PolarsResult<DataFrame> {
+    T::into_dataframe(rows)
+}
+
+// A Rust type stored in a single DataFrame cell
+pub trait ToAnyValue {
+    fn dtype() -> DataType;
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>>;
+}
+
+// A column of values of type T, used by the derived impls
+pub fn column<'a, T: ToAnyValue + 'a>(name: &str, values: impl Iterator<Item = &'a T>) -> PolarsResult<Series> {
+    let values = values.map(T::to_any_value).collect::<PolarsResult<Vec<_>>>()?;
+    Series::from_any_values_and_dtype(name, &values, &T::dtype(), true)
+}
+
+//--------------------
+
+macro_rules! impl_to_any_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl ToAnyValue for $ty {
+                fn dtype() -> DataType {
+                    DataType::$variant
+                }
+                fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+                    Ok(AnyValue::$variant(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_any_value!(
+    bool => Boolean,
+    i8 => Int8,
+    i16 => Int16,
+    i32 => Int32,
+    i64 => Int64,
+    u8 => UInt8,
+    u16 => UInt16,
+    u32 => UInt32,
+    u64 => UInt64,
+    f32 => Float32,
+    f64 => Float64,
+);
+
+impl ToAnyValue for String {
+    fn dtype() -> DataType {
+        DataType::String
+    }
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+        Ok(AnyValue::StringOwned(self.as_str().into()))
+    }
+}
+
+impl ToAnyValue for &str {
+    fn dtype() -> DataType {
+        DataType::String
+    }
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+        Ok(AnyValue::StringOwned((*self).into()))
+    }
+}
+
+// Days since 1970-01-01
+impl ToAnyValue for NaiveDate {
+    fn dtype() -> DataType {
+        DataType::Date
+    }
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+        let days = self.signed_duration_since(NaiveDate::default()).num_days();
+        Ok(AnyValue::Date(days as i32))
+    }
+}
+
+// Microseconds since 1970-01-01 00:00:00, without a time zone
+impl ToAnyValue for NaiveDateTime {
+    fn dtype() -> DataType {
+        DataType::Datetime(TimeUnit::Microseconds, None)
+    }
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+        static NO_TIME_ZONE: Option<TimeZone> = None;
+        Ok(AnyValue::Datetime(self.and_utc().timestamp_micros(), TimeUnit::Microseconds, &NO_TIME_ZONE))
+    }
+}
+
+// None is a null of the dtype of T
+impl<T: ToAnyValue> ToAnyValue for Option<T> {
+    fn dtype() -> DataType {
+        T::dtype()
+    }
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+        match self {
+            Some(value) => value.to_any_value(),
+            None => Ok(AnyValue::Null),
+        }
+    }
+}
+
+impl<T: ToAnyValue> ToAnyValue for Vec<T> {
+    fn dtype() -> DataType {
+        DataType::List(Box::new(T::dtype()))
+    }
+    fn to_any_value(&self) -> PolarsResult<AnyValue<'static>> {
+        Ok(AnyValue::List(column("", self.iter())?))
+    }
+}
+
+//--------------------
+
+// A struct read from the rows of a DataFrame, one field per column
+pub trait FromDataFrame: Sized {
+    fn from_dataframe(df: &DataFrame) -> PolarsResult<Vec<Self>>;
+}
+
+// The rows of a DataFrame as structs deriving FromDataFrame
+pub fn from_dataframe<T: FromDataFrame>(df: &DataFrame) -> PolarsResult<Vec<T>> {
+    T::from_dataframe(df)
+}
+
+// A Rust type read from a single DataFrame cell
+pub trait FromAnyValue: Sized {
+    // Whether a column of this dtype can be read
+    fn accepts(dtype: &DataType) -> bool;
+    fn from_any_value(value: AnyValue) -> PolarsResult<Self>;
+}
+
+// A column of the frame and whether it can be read into a field, for the derived impls
+pub struct ColumnCheck {
+    pub name: &'static str,
+    pub accepts: fn(&DataType) -> bool,
+    pub expected: &'static str, // the Rust type of the field, for the error message
+}
+
+// The columns read into the fields of `target`, in order.
+// Every missing column and every column of the wrong dtype is reported in a single error.
+pub fn columns<'a>(df: &'a DataFrame, target: &str, checks: &[ColumnCheck]) -> PolarsResult<Vec<&'a Series>> {
+    let missing: Vec<&str> = checks
+        .iter()
+        .filter(|check| df.column(check.name).is_err())
+        .map(|check| check.name)
+        .collect();
+    let incompatible: Vec<String> = checks
+        .iter()
+        .filter_map(|check| {
+            let series = df.column(check.name).ok()?;
+            (!(check.accepts)(series.dtype()))
+                .then(|| format!("\"{}\" is {}, cannot be read as {}", check.name, series.dtype(), check.expected))
+        })
+        .collect();
+
+    if missing.is_empty() && incompatible.is_empty() {
+        return Ok(checks.iter().map(|check| df.column(check.name).unwrap()).collect());
+    }
+
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        let columns: Vec<String> = missing.iter().map(|name| format!("\"{}\"", name)).collect();
+        problems.push(format!("missing columns {} (the frame has {:?})", columns.join(", "), df.get_column_names()));
+    }
+    problems.extend(incompatible);
+    polars_bail!(SchemaMismatch: "cannot read DataFrame into {}: {}", target, problems.join("; "))
+}
+
+// The value of a cell, with the row and column in the error
+pub fn cell<T: FromAnyValue>(series: &Series, row: usize) -> PolarsResult<T> {
+    T::from_any_value(series.get(row)?)
+        .map_err(|e| e.context(format!("row {}, column \"{}\"", row, series.name()).into()))
+}
+
+// The fields of a Struct value by name, for the derived impls
+pub fn struct_fields(value: AnyValue) -> PolarsResult<Vec<(String, AnyValue<'static>)>> {
+    let names_and_values: Vec<(String, AnyValue)> = match &value {
+        AnyValue::Struct(_, _, fields) => fields.iter().map(|field| field.name().to_string()).zip(value._iter_struct_av()).collect(),
+        AnyValue::StructOwned(payload) => payload.1.iter().map(|field| field.name().to_string()).zip(payload.0.iter().cloned()).collect(),
+        other => polars_bail!(SchemaMismatch: "expected a struct, found {}", other),
+    };
+    names_and_values
+        .into_iter()
+        .map(|(name, value)| Ok((name, value.into_static()?)))
+        .collect()
+}
+
+pub fn struct_field<T: FromAnyValue>(fields: &[(String, AnyValue<'static>)], name: &str) -> PolarsResult<T> {
+    match fields.iter().find(|(field, _)| field == name) {
+        Some((_, value)) => T::from_any_value(value.clone()).map_err(|e| e.context(format!("field \"{}\"", name).into())),
+        None => polars_bail!(SchemaMismatch: "missing struct field \"{}\"", name),
+    }
+}
+
+//--------------------
+
+// Numbers and booleans are read from their own dtype only, eg a u32 count is not read as i64
+macro_rules! impl_from_any_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl FromAnyValue for $ty {
+                fn accepts(dtype: &DataType) -> bool {
+                    *dtype == DataType::$variant
+                }
+                fn from_any_value(value: AnyValue) -> PolarsResult<Self> {
+                    match value {
+                        AnyValue::$variant(v) => Ok(v),
+                        other => polars_bail!(SchemaMismatch: "expected {}, found {}", stringify!($ty), other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_any_value!(
+    bool => Boolean,
+    i8 => Int8,
+    i16 => Int16,
+    i32 => Int32,
+    i64 => Int64,
+    u8 => UInt8,
+    u16 => UInt16,
+    u32 => UInt32,
+    u64 => UInt64,
+    f32 => Float32,
+    f64 => Float64,
+);
+
+// Strings, categoricals and enums
+impl FromAnyValue for String {
+    fn accepts(dtype: &DataType) -> bool {
+        matches!(dtype, DataType::String | DataType::Categorical(_, _) | DataType::Enum(_, _))
+    }
+    fn from_any_value(value: AnyValue) -> PolarsResult<Self> {
+        match value.get_str() {
+            Some(s) => Ok(s.to_string()),
+            None => polars_bail!(SchemaMismatch: "expected a string, found {}", value),
+        }
+    }
+}
+
+impl FromAnyValue for NaiveDate {
+    fn accepts(dtype: &DataType) -> bool {
+        *dtype == DataType::Date
+    }
+    fn from_any_value(value: AnyValue) -> PolarsResult<Self> {
+        match value {
+            AnyValue::Date(days) => Ok(NaiveDate::default() + chrono::Duration::days(days as i64)),
+            other => polars_bail!(SchemaMismatch: "expected a date, found {}", other),
+        }
+    }
+}
+
+// Any time unit; the time zone, if any, is dropped
+impl FromAnyValue for NaiveDateTime {
+    fn accepts(dtype: &DataType) -> bool {
+        matches!(dtype, DataType::Datetime(_, _))
+    }
+    fn from_any_value(value: AnyValue) -> PolarsResult<Self> {
+        let datetime = match value {
+            AnyValue::Datetime(v, TimeUnit::Nanoseconds, _) => Some(chrono::DateTime::from_timestamp_nanos(v)),
+            AnyValue::Datetime(v, TimeUnit::Microseconds, _) => chrono::DateTime::from_timestamp_micros(v),
+            AnyValue::Datetime(v, TimeUnit::Milliseconds, _) => chrono::DateTime::from_timestamp_millis(v),
+            other => polars_bail!(SchemaMismatch: "expected a datetime, found {}", other),
+        };
+        match datetime {
+            Some(datetime) => Ok(datetime.naive_utc()),
+            None => polars_bail!(ComputeError: "datetime out of range"),
+        }
+    }
+}
+
+// A null is None, in any other field it is an error
+impl<T: FromAnyValue> FromAnyValue for Option<T> {
+    fn accepts(dtype: &DataType) -> bool {
+        T::accepts(dtype) || *dtype == DataType::Null
+    }
+    fn from_any_value(value: AnyValue) -> PolarsResult<Self> {
+        match value {
+            AnyValue::Null => Ok(None),
+            value => T::from_any_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromAnyValue> FromAnyValue for Vec<T> {
+    fn accepts(dtype: &DataType) -> bool {
+        matches!(dtype, DataType::List(inner) if T::accepts(inner))
+    }
+    fn from_any_value(value: AnyValue) -> PolarsResult<Self> {
+        match value {
+            AnyValue::List(s) => s.iter().map(T::from_any_value).collect(),
+            other => polars_bail!(SchemaMismatch: "expected a list, found {}", other),
+        }
+    }
+}
+
+//--------------------
+
+// A Rust enum stored as a Polars Enum column, one category per variant.
+// Implemented by #[derive(PolarsEnum)], which also implements ToAnyValue and
+// FromAnyValue so that the enum can be a field of the structs above:
+//
+//     #[derive(PolarsEnum)]
+//     enum Month { January, February, ... }
+//     let s = Month::to_series("month", &[Month::March, Month::January])?;
+pub trait PolarsEnum: Sized {
+    // The categories, in the order of the variants
+    const NAMES: &'static [&'static str];
+    // Physical sorts by the order of the variants, Lexical by name
+    const ORDERING: CategoricalOrdering;
+
+    fn name(&self) -> &'static str;
+    fn from_name(name: &str) -> Option<Self>;
+
+    fn enum_dtype() -> DataType {
+        let categories = Utf8ViewArray::from_slice_values(Self::NAMES);
+        DataType::Enum(Some(Arc::new(RevMapping::build_local(categories))), Self::ORDERING)
+    }
+
+    fn to_series(name: &str, values: &[Self]) -> PolarsResult<Series> {
+        let names: Vec<&str> = values.iter().map(Self::name).collect();
+        Series::new(name, names).strict_cast(&Self::enum_dtype())
+    }
+
+    // From an Enum, Categorical or String column; nulls and unknown names are errors
+    fn from_series(series: &Series) -> PolarsResult<Vec<Self>> {
+        (0..series.len())
+            .map(|row| {
+                enum_from_any_value(series.get(row)?)
+                    .map_err(|e| e.context(format!("row {}, column \"{}\"", row, series.name()).into()))
+            })
+            .collect()
+    }
+}
+
+// For the FromAnyValue impls of the derived enums
+pub fn enum_accepts(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::Enum(_, _) | DataType::Categorical(_, _) | DataType::String)
+}
+
+pub fn enum_from_any_value<T: PolarsEnum>(value: AnyValue) -> PolarsResult<T> {
+    let enum_name = std::any::type_name::<T>().rsplit("::").next().unwrap_or_default();
+    match value.get_str() {
+        Some(name) => match T::from_name(name) {
+            Some(variant) => Ok(variant),
+            None => polars_bail!(SchemaMismatch: "{:?} is not a {}, expected one of {}", name, enum_name, T::NAMES.join(", ")),
+        },
+        None => polars_bail!(SchemaMismatch: "expected a {}, found {}", enum_name, value),
+    }
+}
+
+//--------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use my_proc_macro::{FromDataFrame, IntoDataFrame};
+
+    #[derive(Debug, Clone, PartialEq, IntoDataFrame, FromDataFrame)]
+    struct Address {
+        city: String,
+        zip: Option<u32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoDataFrame, FromDataFrame)]
+    struct Person {
+        #[polars(rename = "Name")]
+        name: String,
+        age: Option<i32>,
+        born: NaiveDate,
+        seen_at: NaiveDateTime,
+        address: Address,
+        scores: Vec<f64>,
+        previous: Vec<Address>,
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn people() -> Vec<Person> {
+        vec![
+            Person {
+                name: "Ada".to_string(),
+                age: Some(36),
+                born: date(1815, 12, 10),
+                seen_at: date(2024, 3, 1).and_hms_micro_opt(12, 30, 5, 250).unwrap(),
+                address: Address { city: "London".to_string(), zip: None },
+                scores: vec![9.5, 7.25],
+                previous: vec![Address { city: "Marylebone".to_string(), zip: Some(1) }],
+            },
+            Person {
+                name: "Grace".to_string(),
+                age: None,
+                born: date(1970, 1, 2),
+                seen_at: date(1970, 1, 1).and_hms_opt(0, 0, 1).unwrap(),
+                address: Address { city: "Arlington".to_string(), zip: Some(22201) },
+                scores: vec![],
+                previous: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn schema_follows_the_fields() {
+        let df = to_dataframe(&people()).unwrap();
+        let address = DataType::Struct(vec![Field::new("city", DataType::String), Field::new("zip", DataType::UInt32)]);
+        let dtypes: Vec<(&str, DataType)> = df.get_columns().iter().map(|s| (s.name(), s.dtype().clone())).collect();
+        assert_eq!(
+            dtypes,
+            [
+                ("Name", DataType::String),
+                ("age", DataType::Int32),
+                ("born", DataType::Date),
+                ("seen_at", DataType::Datetime(TimeUnit::Microseconds, None)),
+                ("address", address.clone()),
+                ("scores", DataType::List(Box::new(DataType::Float64))),
+                ("previous", DataType::List(Box::new(address))),
+            ]
+        );
+    }
+
+    #[test]
+    fn none_is_null() {
+        let df = to_dataframe(&people()).unwrap();
+        assert_eq!(df.column("age").unwrap().null_count(), 1);
+        assert_eq!(df.column("age").unwrap().get(1).unwrap(), AnyValue::Null);
+
+        let people: Vec<Person> = from_dataframe(&df).unwrap();
+        assert_eq!(people[1].age, None);
+        assert_eq!(people[0].address.zip, None);
+    }
+
+    #[test]
+    fn dates_are_days_and_datetimes_microseconds_since_the_epoch() {
+        let df = to_dataframe(&people()).unwrap();
+        assert_eq!(df.column("born").unwrap().get(1).unwrap(), AnyValue::Date(1));
+        assert_eq!(
+            df.column("seen_at").unwrap().get(1).unwrap(),
+            AnyValue::Datetime(1_000_000, TimeUnit::Microseconds, &None)
+        );
+    }
+
+    #[test]
+    fn datetimes_are_read_in_any_time_unit() {
+        let df = df!("at" => [1_500i64]).unwrap();
+        let df = df.lazy().select([col("at").cast(DataType::Datetime(TimeUnit::Milliseconds, None))]).collect().unwrap();
+        let at: NaiveDateTime = cell(df.column("at").unwrap(), 0).unwrap();
+        assert_eq!(at, date(1970, 1, 1).and_hms_milli_opt(0, 0, 1, 500).unwrap());
+    }
+
+    #[test]
+    fn round_trip() {
+        let people = people();
+        let df = to_dataframe(&people).unwrap();
+        assert_eq!(from_dataframe::<Person>(&df).unwrap(), people);
+    }
+
+    #[test]
+    fn rename_sets_the_column_name() {
+        let df = to_dataframe(&people()).unwrap();
+        assert!(df.column("Name").is_ok());
+        assert!(df.column("name").is_err());
+    }
+
+    #[test]
+    fn empty_input() {
+        let df = to_dataframe::<Person>(&[]).unwrap();
+        assert_eq!(df.shape(), (0, 7));
+        assert_eq!(df.schema(), to_dataframe(&people()).unwrap().schema());
+        assert!(from_dataframe::<Person>(&df).unwrap().is_empty());
+    }
+
+    #[test]
+    fn missing_columns_and_wrong_dtypes_are_reported_together() {
+        let df = df!("Name" => ["Ada"], "age" => ["36"], "born" => [1i32]).unwrap();
+        let message = from_dataframe::<Person>(&df).unwrap_err().to_string();
+        assert!(message.contains("cannot read DataFrame into Person"), "{}", message);
+        assert!(
+            message.contains(r#"missing columns "seen_at", "address", "scores", "previous" (the frame has ["Name", "age", "born"])"#),
+            "{}",
+            message
+        );
+        assert!(message.contains(r#""age" is str, cannot be read as Option<i32>"#), "{}", message);
+        assert!(message.contains(r#""born" is i32, cannot be read as NaiveDate"#), "{}", message);
+    }
+
+    #[test]
+    fn null_in_a_required_field_names_the_cell() {
+        let df = df!("city" => [Some("Oslo"), None], "zip" => [Some(150u32), None]).unwrap();
+        let message = from_dataframe::<Address>(&df).unwrap_err().to_string();
+        assert!(message.contains(r#"row 1, column "city""#), "{}", message);
+    }
+}
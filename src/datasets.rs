@@ -0,0 +1,210 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use polars::prelude::*;
+
+use crate::download::{download_file, verify, DownloadError};
+use crate::paths;
+
+// The datasets read by the examples, each parsed the same way in every chapter.
+// The file in the data directory is used when it is complete, then the copy in src/
+// of the crate: a file matching the digest, or for a dataset without one, a CSV file
+// whose records all have their fields. Otherwise a dataset with a url is downloaded to the data directory
+// on first use, see crate::download, and an embedded one is written there from the binary.
+//
+//     let iris = datasets::load("iris")?.filter(col("sepal_length").gt(lit(5))).collect()?;
+
+pub struct Dataset {
+    pub name: &'static str,
+    pub url: Option<&'static str>,
+    pub sha256: Option<&'static str>, // None when the file changes upstream
+    pub file: &'static str, // in src/ of the crate, or in the data directory
+    pub embedded: Option<&'static [u8]>, // built into the binary, for datasets without a url
+    pub dtypes: &'static [(&'static str, DataType)], // overrides of the inferred dtypes
+}
+
+const CATEGORICAL: DataType = DataType::Categorical(None, CategoricalOrdering::Physical);
+
+pub static DATASETS: &[Dataset] = &[
+    Dataset {
+        name: "iris",
+        url: None,
+        sha256: None,
+        file: "iris.csv",
+        embedded: Some(include_bytes!("iris.csv")),
+        dtypes: &[],
+    },
+    Dataset {
+        name: "legislators-historical",
+        url: Some("https://theunitedstates.io/congress-legislators/legislators-historical.csv"),
+        // The list grows as legislators leave office, so there is no fixed digest to check.
+        // The file is checked to be a complete CSV instead, see complete_csv.
+        sha256: None,
+        file: "ex_014_aggregation_legislators-historical.csv",
+        embedded: None,
+        dtypes: &[
+            ("first_name", CATEGORICAL),
+            ("last_name", CATEGORICAL),
+            ("gender", CATEGORICAL),
+            ("type", CATEGORICAL),
+            ("state", CATEGORICAL),
+            ("party", CATEGORICAL),
+            ("birthday", DataType::Date),
+        ],
+    },
+    Dataset {
+        name: "pokemon",
+        url: Some("https://gist.githubusercontent.com/ritchie46/cac6b337ea52281aa23c049250a4ff03/raw/89a957ff3919d90e6ef2d34235e6bf22304f3366/pokemon.csv"),
+        // The gist is pinned to a revision, so the file never changes
+        sha256: Some("2b6a5442100a742ee635586d8a1b975e8aa05ac4291011489ccec12390e1d3d6"),
+        file: "ex_016_aggregation_pokemon.csv",
+        embedded: None,
+        dtypes: &[],
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Dataset> {
+    DATASETS.iter().find(|dataset| dataset.name == name)
+}
+
+// Datasets already downloaded or checked by this process, and their files
+static READY: Mutex<Vec<(&'static str, PathBuf)>> = Mutex::new(Vec::new());
+
+// The local file of a dataset, downloaded first if needed
+pub fn path(name: &str) -> PolarsResult<PathBuf> {
+    let Some(dataset) = find(name) else {
+        let names: Vec<&str> = DATASETS.iter().map(|dataset| dataset.name).collect();
+        polars_bail!(ComputeError: "unknown dataset {:?}, expected one of {}", name, names.join(", "));
+    };
+
+    let mut ready = READY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((_, path)) = ready.iter().find(|(ready, _)| *ready == dataset.name) {
+        return Ok(path.clone());
+    }
+
+    // The data directory first, so that --data-dir or its variable is honoured
+    let local = paths::data_file(dataset.file)?;
+    let bundled = paths::bundled_file(dataset.file);
+    let path = if local.exists() && usable(dataset, &local)? {
+        local
+    } else if let Some(bundled) = bundled.filter(|bundled| usable(dataset, bundled).unwrap_or(false)) {
+        bundled
+    } else if let Some(url) = dataset.url {
+        // download_file keeps an existing file it has no digest for
+        if local.exists() && dataset.sha256.is_none() {
+            println!("File at {:?} is incomplete, downloading it again", local);
+            fs::remove_file(&local)?;
+        }
+        // Named after the dataset, eg "dataset pokemon: ... is missing and --offline is set"
+        let failed = |reason: String| DownloadError::new(url, format!("dataset {}: {}", dataset.name, reason));
+        download_file(url, &local, dataset.sha256).map_err(|e| failed(e.reason))?;
+        if !usable(dataset, &local)? {
+            return Err(failed(format!("{} is not a complete CSV file", local.display())).into());
+        }
+        local
+    } else if let Some(bytes) = dataset.embedded {
+        fs::write(&local, bytes)?;
+        println!("Wrote the copy of {} built into the binary to: {:?}", dataset.file, local);
+        local
+    } else {
+        let message = format!("dataset {} not found at {}", dataset.name, local.display());
+        return Err(io::Error::new(io::ErrorKind::NotFound, message).into());
+    };
+
+    ready.push((dataset.name, path.clone()));
+    Ok(path)
+}
+
+// Whether a file on disk holds the whole dataset: it matches the digest, or without
+// one, it is a complete CSV file
+fn usable(dataset: &Dataset, path: &Path) -> io::Result<bool> {
+    match dataset.sha256 {
+        Some(_) => verify(path, dataset.sha256),
+        None => complete_csv(path),
+    }
+}
+
+// Whether every record of a CSV file has as many fields as its header, with no quote
+// left open. A file cut short usually ends in the middle of a record. A cut between
+// two records goes unnoticed here, the Content-Length check of crate::download catches it.
+fn complete_csv(path: &Path) -> io::Result<bool> {
+    let bytes = fs::read(path)?;
+    let mut header_fields = None;
+    let mut fields = 1;
+    let mut empty = true;
+    let mut quoted = false;
+
+    let mut end_record = |fields: usize, empty: bool| {
+        empty || *header_fields.get_or_insert(fields) == fields
+    };
+    for &byte in &bytes {
+        match byte {
+            b'"' => quoted = !quoted, // an escaped "" flips twice
+            b',' if !quoted => fields += 1,
+            b'\n' if !quoted => {
+                if !end_record(fields, empty) {
+                    return Ok(false);
+                }
+                fields = 1;
+                empty = true;
+                continue;
+            }
+            b'\r' if !quoted => continue,
+            _ => {}
+        }
+        empty = false;
+    }
+    // The last record may lack its newline
+    Ok(!quoted && end_record(fields, empty) && !bytes.is_empty())
+}
+
+// A lazy scan of the dataset, with its dtype overrides applied
+pub fn load(name: &str) -> PolarsResult<LazyFrame> {
+    let path = path(name)?;
+    let dataset = find(name).expect("checked by path()");
+
+    let dtypes: Schema = dataset
+        .dtypes
+        .iter()
+        .map(|(column, dtype)| Field::new(column, dtype.clone()))
+        .collect();
+
+    LazyCsvReader::new(path)
+        .with_has_header(true)
+        .with_infer_schema_length(Some(100))
+        .with_try_parse_dates(true)
+        .with_dtype_overwrite((!dtypes.is_empty()).then(|| Arc::new(dtypes)))
+        .finish()
+}
+
+//--------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, text: &str) -> bool {
+        let path = std::env::temp_dir().join(format!("polars-rust-examples-{}-{}.csv", name, std::process::id()));
+        fs::write(&path, text).unwrap();
+        let complete = complete_csv(&path).unwrap();
+        fs::remove_file(&path).ok();
+        complete
+    }
+
+    #[test]
+    fn complete_csv_accepts_whole_files() {
+        assert!(check("whole", "a,b,c\n1,\"x, y\",3\n4,\"say \"\"hi\"\"\",6\n"));
+        assert!(check("no-final-newline", "a,b\r\n1,2\r\n3,4"));
+        let bundled = paths::bundled_file("ex_014_aggregation_legislators-historical.csv").unwrap();
+        assert!(complete_csv(&bundled).unwrap());
+    }
+
+    #[test]
+    fn complete_csv_rejects_truncated_files() {
+        assert!(!check("empty", ""));
+        assert!(!check("short-record", "a,b,c\n1,2,3\n4,5"));
+        assert!(!check("open-quote", "a,b\n1,\"x, y"));
+    }
+}
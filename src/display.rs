@@ -0,0 +1,123 @@
+use std::env;
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+use polars::prelude::*;
+
+// How DataFrames are printed, set once from the command line.
+// The table options are handed to Polars through its POLARS_FMT_* variables, so they
+// apply to every frame printed with {:?} or {}, not only to print_data.
+// An option left unset keeps the value of the variable in the environment.
+
+// Polars table presets, as POLARS_FMT_TABLE_FORMATTING spells them in lowercase with dashes
+pub const STYLES: &[&str] = &[
+    "utf8-full-condensed",
+    "utf8-full",
+    "utf8-no-borders",
+    "utf8-borders-only",
+    "utf8-horizontal-only",
+    "ascii-full",
+    "ascii-full-condensed",
+    "ascii-no-borders",
+    "ascii-borders-only",
+    "ascii-borders-only-condensed",
+    "ascii-horizontal-only",
+    "ascii-markdown",
+];
+
+// As in Polars
+const DEFAULT_ROW_LIMIT: usize = 10;
+const DEFAULT_STR_LEN: usize = 30;
+
+// Stand-ins for "no limit" where Polars would overflow on -1 or u16::MAX
+const ALL_CHARS: i64 = 1_000_000;
+const FULL_TABLE_WIDTH: u16 = 10_000;
+
+#[derive(Debug, Clone, Default)]
+pub struct DisplayOptions {
+    pub max_rows: Option<i64>, // -1 shows every row
+    pub max_cols: Option<i64>, // -1 shows every column
+    pub float_precision: Option<usize>,
+    pub str_len: Option<i64>,  // characters shown of a string, -1 for all
+    pub full_width: bool,      // every column and whole strings, however wide the table gets
+    pub style: Option<String>, // one of STYLES
+    pub vertical: bool,        // one block per record instead of a table
+}
+
+static OPTIONS: OnceLock<DisplayOptions> = OnceLock::new();
+
+// Set once from the command line, before any example runs
+pub fn configure(options: DisplayOptions) {
+    let all = options.full_width.then_some(-1);
+    let vars = [
+        ("POLARS_FMT_MAX_ROWS", options.max_rows.map(|n| n.to_string())),
+        ("POLARS_FMT_MAX_COLS", options.max_cols.or(all).map(|n| n.to_string())),
+        ("POLARS_FMT_STR_LEN", options.str_len.or(all).map(|n| if n < 0 { ALL_CHARS } else { n }.to_string())),
+        ("POLARS_TABLE_WIDTH", options.full_width.then(|| FULL_TABLE_WIDTH.to_string())),
+        ("POLARS_FMT_TABLE_FORMATTING", options.style.as_ref().map(|style| style.replace('-', "_").to_uppercase())),
+    ];
+    for (name, value) in vars {
+        if let Some(value) = value {
+            env::set_var(name, value);
+        }
+    }
+    if options.float_precision.is_some() {
+        polars_core::fmt::set_float_precision(options.float_precision);
+    }
+    OPTIONS.set(options).ok();
+}
+
+pub fn vertical() -> bool {
+    OPTIONS.get().is_some_and(|options| options.vertical)
+}
+
+// Rows shown of a DataFrame, from --max-rows or POLARS_FMT_MAX_ROWS
+pub fn row_limit() -> usize {
+    env_limit("POLARS_FMT_MAX_ROWS").unwrap_or(DEFAULT_ROW_LIMIT)
+}
+
+// One block per row, a line per column, for rows too wide for a table:
+//
+//     -[ RECORD 1 ]-----------
+//     first_name | Richard
+//     last_name  | Bassett
+//
+// Rows and strings are limited like the table, by POLARS_FMT_MAX_ROWS and POLARS_FMT_STR_LEN.
+pub fn records(df: &DataFrame) -> String {
+    let max_rows = row_limit();
+    let str_len = env_limit("POLARS_FMT_STR_LEN").unwrap_or(DEFAULT_STR_LEN);
+    let name_width = df.get_column_names().iter().map(|name| name.chars().count()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for row in 0..df.height().min(max_rows) {
+        writeln!(out, "{:-<width$}", format!("-[ RECORD {} ]", row + 1), width = name_width + 20).unwrap();
+        for s in df.get_columns() {
+            let value = match s.get(row) {
+                Ok(value) => cell(&value, str_len),
+                Err(e) => e.to_string(),
+            };
+            writeln!(out, "{:<width$} | {}", s.name(), value, width = name_width).unwrap();
+        }
+    }
+    if df.height() > max_rows {
+        writeln!(out, "... {} more records", df.height() - max_rows).unwrap();
+    }
+    out
+}
+
+// Strings unquoted and cut to the limit, other values as Polars shows them in a table
+fn cell(value: &AnyValue, str_len: usize) -> String {
+    match value.get_str() {
+        Some(text) if text.chars().count() > str_len => format!("{}…", text.chars().take(str_len).collect::<String>()),
+        Some(text) => text.to_string(),
+        None => value.to_string(),
+    }
+}
+
+// A Polars limit variable: unset for the default, negative for no limit
+fn env_limit(name: &str) -> Option<usize> {
+    match env::var(name).ok()?.parse::<i64>().ok()? {
+        n if n < 0 => Some(usize::MAX),
+        n => Some(n as usize),
+    }
+}
@@ -0,0 +1,273 @@
+use std::fs::{self, File};
+use std::io::{stderr, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use polars::prelude::PolarsError;
+// Need to enable the "blocking" feature for the reqwest crate
+use reqwest::blocking::Client;
+use sha2::{Digest, Sha256};
+
+// Downloads of the datasets used by the examples.
+// A file is written next to its final path as <name>.part and renamed once it is
+// complete, as long as the Content-Length, and matches its SHA-256 digest, so an
+// interrupted download never leaves a truncated file behind. Failed attempts are retried with backoff.
+
+#[derive(Debug)]
+pub struct DownloadError {
+    pub url: String,
+    pub reason: String,
+}
+
+impl DownloadError {
+    pub fn new(url: &str, reason: impl std::fmt::Display) -> Self {
+        DownloadError {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to download {}: {}", self.url, self.reason)
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+// Carried as an IO error, so examples can use `?` and the runner can still
+// tell a failed download apart from other errors
+impl From<DownloadError> for PolarsError {
+    fn from(e: DownloadError) -> Self {
+        std::io::Error::other(e).into()
+    }
+}
+
+//--------------------
+
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    pub offline: bool,     // fail instead of downloading a missing file
+    pub timeout: Duration, // per attempt, body included
+    pub attempts: u32,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        DownloadOptions {
+            offline: false,
+            timeout: Duration::from_secs(60),
+            attempts: 3,
+        }
+    }
+}
+
+static OPTIONS: OnceLock<DownloadOptions> = OnceLock::new();
+
+// Set once from the command line, before any example runs
+pub fn configure(options: DownloadOptions) {
+    OPTIONS.set(options).ok();
+}
+
+fn options() -> &'static DownloadOptions {
+    OPTIONS.get_or_init(DownloadOptions::default)
+}
+
+// Download url to output_path unless a file is already there.
+// With a digest, an existing file is checked too and downloaded again if it does not match.
+pub fn download_file(url: &str, output_path: impl AsRef<Path>, sha256: Option<&str>) -> Result<(), DownloadError> {
+    let path = output_path.as_ref();
+    if path.exists() {
+        match sha256 {
+            None => {
+                println!("File already exists at: {:?}", path);
+                return Ok(());
+            }
+            Some(_) if verify(path, sha256).map_err(|e| DownloadError::new(url, e))? => {
+                println!("File already exists at: {:?} (checksum ok)", path);
+                return Ok(());
+            }
+            Some(_) => println!("File at {:?} does not match its checksum, downloading it again", path),
+        }
+    }
+
+    let options = options();
+    if options.offline {
+        let problem = if path.exists() { "does not match its checksum" } else { "is missing" };
+        let reason = format!("{} {} and --offline is set, run without --offline to download it", path.display(), problem);
+        return Err(DownloadError::new(url, reason));
+    }
+
+    let mut attempt = 1;
+    loop {
+        match fetch(url, path, sha256, options) {
+            Ok(()) => break,
+            Err(failure) if failure.retry && attempt < options.attempts => {
+                // 1s, 2s, 4s, ...
+                let backoff = Duration::from_secs(1 << (attempt - 1));
+                eprintln!(
+                    "Download of {} failed ({}), attempt {}/{}, retrying in {}s",
+                    url,
+                    failure.reason,
+                    attempt,
+                    options.attempts,
+                    backoff.as_secs()
+                );
+                thread::sleep(backoff);
+                attempt += 1;
+            }
+            Err(failure) => return Err(DownloadError::new(url, failure.reason)),
+        }
+    }
+
+    println!("File downloaded successfully to: {:?}", path);
+    Ok(())
+}
+
+// Why an attempt failed, and whether another one may succeed
+struct Failure {
+    reason: String,
+    retry: bool,
+}
+
+impl Failure {
+    fn retry(reason: impl std::fmt::Display) -> Self {
+        Failure { reason: reason.to_string(), retry: true }
+    }
+
+    fn fatal(reason: impl std::fmt::Display) -> Self {
+        Failure { reason: reason.to_string(), retry: false }
+    }
+}
+
+fn fetch(url: &str, path: &Path, sha256: Option<&str>, options: &DownloadOptions) -> Result<(), Failure> {
+    let part = part_path(path);
+    let result = fetch_to(url, &part, sha256, options).and_then(|()| fs::rename(&part, path).map_err(Failure::fatal));
+    if result.is_err() {
+        fs::remove_file(&part).ok();
+    }
+    result
+}
+
+fn fetch_to(url: &str, part: &Path, sha256: Option<&str>, options: &DownloadOptions) -> Result<(), Failure> {
+    let client = Client::builder().timeout(options.timeout).build().map_err(Failure::fatal)?;
+    let mut response = client.get(url).send().map_err(Failure::retry)?;
+
+    // Server errors and rate limiting may pass, a 404 will not
+    let status = response.status();
+    if !status.is_success() {
+        let reason = format!("HTTP {}", status);
+        return Err(if status.is_server_error() || status.as_u16() == 429 { Failure::retry(reason) } else { Failure::fatal(reason) });
+    }
+
+    let mut file = File::create(part).map_err(Failure::fatal)?;
+    let mut hasher = Sha256::new();
+    let expected_len = response.content_length();
+    let mut progress = Progress::new(path_name(part), expected_len);
+    let mut buffer = vec![0; 64 * 1024];
+    let mut len = 0;
+    loop {
+        let n = response.read(&mut buffer).map_err(Failure::retry)?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&buffer[..n]).map_err(Failure::fatal)?;
+        hasher.update(&buffer[..n]);
+        progress.advance(n as u64);
+        len += n as u64;
+    }
+    progress.finish();
+    file.sync_all().map_err(Failure::fatal)?;
+
+    // Without a digest, the only sign of a connection closed early
+    if let Some(expected_len) = expected_len.filter(|expected_len| *expected_len != len) {
+        return Err(Failure::retry(format!("incomplete download, got {} of {} bytes", len, expected_len)));
+    }
+
+    if let Some(expected) = sha256 {
+        let actual = hex(&hasher.finalize());
+        if actual != expected.to_lowercase() {
+            return Err(Failure::retry(format!("checksum mismatch, expected sha256 {} but got {}", expected, actual)));
+        }
+    }
+    Ok(())
+}
+
+// data.csv -> data.csv.part, in the same directory so the rename stays on one file system
+fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+fn path_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().trim_end_matches(".part").to_string()
+}
+
+// Whether the file has the expected SHA-256 digest, any content passes without one
+pub fn verify(path: &Path, sha256: Option<&str>) -> std::io::Result<bool> {
+    match sha256 {
+        Some(expected) => Ok(file_digest(path)? == expected.to_lowercase()),
+        None => Ok(true),
+    }
+}
+
+fn file_digest(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex(&hasher.finalize()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+//--------------------
+
+// One line on stderr, redrawn at most 10 times a second, for files over 1 MB or of unknown size.
+// Nothing is shown when stderr is not a terminal.
+struct Progress {
+    name: String,
+    total: Option<u64>,
+    done: u64,
+    visible: bool,
+    last_draw: Option<Instant>,
+}
+
+impl Progress {
+    fn new(name: String, total: Option<u64>) -> Self {
+        let large = total.is_none_or(|total| total > 1_000_000);
+        Progress { name, total, done: 0, visible: large && stderr().is_terminal(), last_draw: None }
+    }
+
+    fn advance(&mut self, n: u64) {
+        self.done += n;
+        if self.visible && self.last_draw.is_none_or(|last| last.elapsed() >= Duration::from_millis(100)) {
+            self.draw();
+            self.last_draw = Some(Instant::now());
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.visible {
+            self.draw();
+            eprintln!();
+        }
+    }
+
+    fn draw(&self) {
+        let done = self.done as f64 / 1e6;
+        match self.total {
+            Some(total) if total > 0 => {
+                let percent = self.done * 100 / total;
+                eprint!("\rDownloading {}: {:.1} / {:.1} MB ({}%)", self.name, done, total as f64 / 1e6, percent);
+            }
+            _ => eprint!("\rDownloading {}: {:.1} MB", self.name, done),
+        }
+        stderr().flush().ok();
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::io;
+use std::process::ExitCode;
+
+use polars::prelude::*;
+
+use crate::download::DownloadError;
+
+// Why an example failed
+#[derive(Debug)]
+pub enum Error {
+    Polars(PolarsError),
+    Panic(String),
+    MissingInput(&'static str), // the example producing an input did not succeed
+}
+
+// Broad classes of failure, each with its own process exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    DataNotFound,
+    Download,
+    Compute,
+}
+
+impl ErrorKind {
+    // 0 is success, 1 is reserved for generic failures and 2 for usage errors
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            ErrorKind::DataNotFound => ExitCode::from(3),
+            ErrorKind::Download => ExitCode::from(4),
+            ErrorKind::Compute => ExitCode::from(5),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorKind::DataNotFound => "data not found",
+            ErrorKind::Download => "download failure",
+            ErrorKind::Compute => "compute error",
+        }
+    }
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Polars(e) => match io_error(e) {
+                Some(io) if io.get_ref().is_some_and(|inner| inner.is::<DownloadError>()) => ErrorKind::Download,
+                Some(io) if io.kind() == io::ErrorKind::NotFound => ErrorKind::DataNotFound,
+                _ => ErrorKind::Compute,
+            },
+            Error::Panic(_) | Error::MissingInput(_) => ErrorKind::Compute,
+        }
+    }
+
+    // Name of the PolarsError variant, eg "ComputeError"
+    pub fn variant(&self) -> &'static str {
+        match self {
+            Error::Polars(e) => polars_variant(e),
+            Error::Panic(_) => "panic",
+            Error::MissingInput(_) => "missing input",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Polars(e) => write!(f, "{}", e),
+            Error::Panic(message) => write!(f, "panicked: {}", message),
+            Error::MissingInput(name) => write!(f, "depends on {}, which did not succeed", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<PolarsError> for Error {
+    fn from(e: PolarsError) -> Self {
+        Error::Polars(e)
+    }
+}
+
+//--------------------
+
+// The io::Error behind a PolarsError, looking through added context
+fn io_error(e: &PolarsError) -> Option<&io::Error> {
+    match e {
+        PolarsError::IO { error, .. } => Some(error.as_ref()),
+        PolarsError::Context { error, .. } => io_error(error),
+        _ => None,
+    }
+}
+
+fn polars_variant(e: &PolarsError) -> &'static str {
+    match e {
+        PolarsError::ColumnNotFound(_) => "ColumnNotFound",
+        PolarsError::ComputeError(_) => "ComputeError",
+        PolarsError::Duplicate(_) => "Duplicate",
+        PolarsError::InvalidOperation(_) => "InvalidOperation",
+        PolarsError::IO { .. } => "IO",
+        PolarsError::NoData(_) => "NoData",
+        PolarsError::OutOfBounds(_) => "OutOfBounds",
+        PolarsError::SchemaFieldNotFound(_) => "SchemaFieldNotFound",
+        PolarsError::SchemaMismatch(_) => "SchemaMismatch",
+        PolarsError::ShapeMismatch(_) => "ShapeMismatch",
+        PolarsError::SQLInterface(_) => "SQLInterface",
+        PolarsError::SQLSyntax(_) => "SQLSyntax",
+        PolarsError::StringCacheMismatch(_) => "StringCacheMismatch",
+        PolarsError::StructFieldNotFound(_) => "StructFieldNotFound",
+        PolarsError::Context { error, .. } => polars_variant(error),
+    }
+}
@@ -3,6 +3,10 @@ use std::fs::File;
 use chrono::prelude::*; // need to add this crate via cargo
 
 use crate::utilities::*;
+use crate::datasets;
+use crate::paths;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 //-----
@@ -11,9 +15,10 @@ use crate::print_function;
 // # Example
 // https://docs.pola.rs/
 
+#[example(chapter = 1, id = 0, title = "Lazy query over iris.csv")]
 pub fn ex000() -> PolarsResult<()> { // alias for `Result<T, PolarsError>`
     // https://docs.pola.rs/
-    let q = LazyCsvReader::new("src/iris.csv")
+    let q = LazyCsvReader::new(datasets::path("iris")?) // the bundled iris.csv
     .with_has_header(true) // specifies the CSV has a header row
     .finish()?// finalizes the LazyCsvReader config
     .filter(col("sepal_length").gt(lit(5)))// apply lazy operations
@@ -41,6 +46,7 @@ pub fn ex000() -> PolarsResult<()> { // alias for `Result<T, PolarsError>`
 // Type Alias polars::error::PolarsResult
 // https://docs.pola.rs/api/rust/dev/polars/error/type.PolarsResult.html
 
+#[example(chapter = 1, id = 1, title = "Create sample DataFrame for expressions")]
 pub fn ex001() -> PolarsResult<DataFrame> {
 
     let date_series = Series::new("datetime",
@@ -84,9 +90,11 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 // https://docs.pola.rs/user-guide/getting-started/#reading-writing
 // .finish(): https://www.perplexity.ai/search/in-rust-polars-what-is-finish-NjK4jodNR9SCd2vxn_XQ.Q
 
+#[example(chapter = 1, id = 2, title = "Write to CSV and read it back", depends_on = "ex001")]
 pub fn ex002(df_input: &DataFrame) -> PolarsResult<DataFrame> {
-    let mut file = File::create("src/output.csv")
-                    .expect("Could not create file");
+    // Written to the output directory, see --out-dir
+    let path = paths::out_file("output.csv")?;
+    let mut file = File::create(&path)?;
     let df = &mut df_input.clone();
 
     CsvWriter::new(&mut file)
@@ -97,7 +105,7 @@ pub fn ex002(df_input: &DataFrame) -> PolarsResult<DataFrame> {
     let df_csv = CsvReadOptions::default()
         .with_infer_schema_length(None)
         .with_has_header(true)
-        .try_into_reader_with_file_path(Some("src/output.csv".into()))?
+        .try_into_reader_with_file_path(Some(path))?
         .finish()?;
 
     print_function!();
@@ -115,6 +123,7 @@ pub fn ex002(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 // * Select
 // https://docs.pola.rs/user-guide/getting-started/#select
 
+#[example(chapter = 1, id = 3, title = "Select all columns", depends_on = "ex001")]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df.clone()
                 .lazy()
@@ -129,6 +138,7 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 
 // Specifying the specific columns to return via select
+#[example(chapter = 1, id = 4, title = "Select specific columns", depends_on = "ex001")]
 pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df.clone()
                 .lazy()
@@ -145,6 +155,7 @@ pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
 // * Filter
 // https://docs.pola.rs/user-guide/getting-started/#filter
 
+#[example(chapter = 1, id = 5, title = "Filter on a date range", depends_on = "ex001")]
 pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let start_date = NaiveDate::from_ymd_opt(2025, 12, 2)
                     .unwrap()
@@ -174,6 +185,7 @@ pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 // More complex filters that include multiple columns
 
+#[example(chapter = 1, id = 6, title = "Filter on multiple columns", depends_on = "ex001")]
 pub fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
                 .clone()
@@ -193,6 +205,7 @@ pub fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
 // * Add columns
 // https://docs.pola.rs/user-guide/getting-started/#add-columns
 
+#[example(chapter = 1, id = 7, title = "Add columns", depends_on = "ex001")]
 pub fn ex007(df_input: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df_input
             .clone()
@@ -214,6 +227,7 @@ pub fn ex007(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 // * Group by
 // https://docs.pola.rs/user-guide/getting-started/#group-by
 
+#[example(chapter = 1, id = 8, title = "Create sample DataFrame for group by")]
 pub fn ex008() -> PolarsResult<DataFrame> {
     let df2: DataFrame = df!(
         "x" => 0..8,
@@ -228,6 +242,7 @@ pub fn ex008() -> PolarsResult<DataFrame> {
 }
 
 
+#[example(chapter = 1, id = 9, title = "Group by with len", depends_on = "ex008")]
 pub fn ex009(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 
     let out = df_input
@@ -244,6 +259,7 @@ pub fn ex009(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 }
 
 
+#[example(chapter = 1, id = 10, title = "Group by with count and sum", depends_on = "ex008")]
 pub fn ex010(df_input: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df_input
                 .clone()
@@ -265,6 +281,7 @@ pub fn ex010(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 // * Combination
 // https://docs.pola.rs/user-guide/getting-started/#combination
 
+#[example(chapter = 1, id = 11, title = "Combine with_columns and select", depends_on = "ex001")]
 pub fn ex011(df_input: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df_input
             .clone()
@@ -284,6 +301,7 @@ pub fn ex011(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 }
 
 
+#[example(chapter = 1, id = 12, title = "Combine with_columns and select, keeping dates", depends_on = "ex001")]
 pub fn ex012(df_input: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df_input
                 .clone()
@@ -310,6 +328,7 @@ pub fn ex012(df_input: &DataFrame) -> PolarsResult<DataFrame> {
 use rand::Rng;
 
 // create df3
+#[example(chapter = 1, id = 13, title = "Create df3 for join")]
 pub fn ex013() -> PolarsResult<DataFrame> {
     let mut rng = rand::thread_rng();
     let df3: DataFrame = df!(
@@ -326,6 +345,7 @@ pub fn ex013() -> PolarsResult<DataFrame> {
 
 
 // create df4
+#[example(chapter = 1, id = 14, title = "Create df4 for join")]
 pub fn ex014() -> PolarsResult<DataFrame> {
     let df4: DataFrame = df!(
         "x" => 0..8,
@@ -341,8 +361,9 @@ pub fn ex014() -> PolarsResult<DataFrame> {
 
 // Join
 // https://docs.pola.rs/user-guide/getting-started/#join
+#[example(chapter = 1, id = 15, title = "Join", depends_on = ["ex013", "ex014"])]
 pub fn ex015(df3: &DataFrame, df4: &DataFrame) -> PolarsResult<DataFrame> {
-    let joined = df3.join(&df4, ["a"], ["x"], JoinType::Left.into())?;
+    let joined = df3.join(df4, ["a"], ["x"], JoinType::Left.into())?;
 
     print_function!();
     print_type(&joined);
@@ -353,6 +374,7 @@ pub fn ex015(df3: &DataFrame, df4: &DataFrame) -> PolarsResult<DataFrame> {
 
 // Concat
 // https://docs.pola.rs/user-guide/getting-started/#concat
+#[example(chapter = 1, id = 16, title = "Concat", depends_on = ["ex013", "ex014"])]
 pub fn ex016(df3: &DataFrame, df4: &DataFrame) -> PolarsResult<DataFrame> {
     let stacked = df3.hstack(df4.get_columns())?;
     print_function!();
@@ -363,63 +385,12 @@ pub fn ex016(df3: &DataFrame, df4: &DataFrame) -> PolarsResult<DataFrame> {
 
 //---------------------------------
 
-pub fn run(flag: Option<&str>) {
-    println!("001 Getting Started examples");
-    println!("https://docs.pola.rs/user-guide/getting-started/");
-
-    // let df_csv = ex000().unwrap();
-    // hr2();
-    let df1 = ex001().unwrap(); // create df1 for expressions
-    hr2();
-    pause();
-
-    let result2 = ex002(&df1).unwrap(); // write to file
-    hr2();
-    pause();
-
-    let result3 = ex003(&df1).unwrap(); // expression: select all
-    hr2();
-    pause();
-
-    let result4 = ex004(&df1).unwrap(); // expression: select
-    hr2();
-    pause();
-
-    let result5 = ex005(&df1).unwrap(); // expression: simple filter
-    hr2();
-    let result6 = ex006(&df1).unwrap(); // expression: complicated filter
-    hr2();
-    pause();
-
-    let result7 = ex007(&df1).unwrap(); // expression: add columns
-    hr2();
-    pause();
-
-
-    let df2 = ex008().unwrap(); // create df1 for group_by
-    hr2();
-    let result9 = ex009(&df2).unwrap(); // group_by
-    hr2();
-    let result10 = ex010(&df2).unwrap(); // group_by
-    hr2();
-    pause();
-
-
-    let result11 = ex011(&df1).unwrap(); // combination
-    hr2();
-    let result12 = ex012(&df1).unwrap(); // combination
-    hr2();
-    pause();
-
-
-    let df3 = ex013().unwrap(); // create df3 for join
-    hr2();
-    pause();
-    let df4 = ex014().unwrap(); // create df4 for join
-    hr2();
-    pause();
-    let result15 = ex015(&df3, &df4).unwrap(); // join
-    hr2();
-    pause();
-    let result16 = ex016(&df3, &df4).unwrap(); // concat
+inventory::submit! {
+    ChapterInfo {
+        id: 1,
+        name: "ex_001_getting_started",
+        title: "Getting Started",
+        url: "https://docs.pola.rs/user-guide/getting-started/",
+        features: &["lazy"],
+    }
 }
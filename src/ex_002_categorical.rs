@@ -3,10 +3,13 @@ use polars::datatypes::CategoricalOrdering::*;
 use polars_arrow::array::*;
 
 use crate::utilities::*;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 //-----
 
+#[example(chapter = 2, id = 1, title = "Append categoricals without string cache", snapshot)]
 pub fn ex001() -> PolarsResult<Series> {
     print_function!();
     println!("Without string cache");
@@ -37,6 +40,7 @@ pub fn ex001() -> PolarsResult<Series> {
 
 
 
+#[example(chapter = 2, id = 2, title = "Append categoricals using the string cache", snapshot)]
 pub fn ex002() -> PolarsResult<Series> {
     print_function!();
     println!("Using string cache");
@@ -62,19 +66,21 @@ pub fn ex002() -> PolarsResult<Series> {
     print_type(&out);
     println!("{:?}", out);
 
+    // The string cache is disabled again once the holder is dropped
+    drop(_sc);
+    println!("String cache enabled: {}", polars::using_string_cache());
+
     Ok(out)
 }
 
 //----
 
-pub fn run(flag: Option<&str>) {
-    println!("002 Categorical examples");
-    println!("https://docs.pola.rs/user-guide/concepts/data-types/categoricals/#categorical-data-type");
-
-    let cat_result1 = ex001().unwrap();
-    hr2();
-    pause();
-
-    let cat_result1 = ex002().unwrap();
-    println!("String cache enabled: {}", polars::using_string_cache());
+inventory::submit! {
+    ChapterInfo {
+        id: 2,
+        name: "ex_002_categorical",
+        title: "Categorical",
+        url: "https://docs.pola.rs/user-guide/concepts/data-types/categoricals/#categorical-data-type",
+        features: &["dtype-categorical"],
+    }
 }
@@ -3,6 +3,9 @@ use polars::datatypes::CategoricalOrdering::*;
 use polars_arrow::array::*;
 
 use crate::utilities::*;
+use crate::convert::{from_dataframe, to_dataframe, PolarsEnum};
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, FromDataFrame, IntoDataFrame, PolarsEnum};
 use crate::print_function;
 
 //-----
@@ -10,6 +13,7 @@ use crate::print_function;
 
 // https://github.com/pola-rs/polars/issues/14084#issuecomment-2049697806
 // Create enum-backed DataFrame
+#[example(chapter = 3, id = 1, title = "Create enum-backed DataFrame", snapshot)]
 pub fn ex001() -> PolarsResult<DataFrame> {
     print_function!();
 
@@ -106,6 +110,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 
 // https://github.com/pola-rs/polars/issues/14084#issuecomment-2058929518
 // Create enum-backed Series
+#[example(chapter = 3, id = 2, title = "Create enum-backed Series", snapshot)]
 pub fn ex002() -> PolarsResult<Series>  {
     print_function!();
 
@@ -236,6 +241,7 @@ pub fn ex002() -> PolarsResult<Series>  {
 
 // https://docs.pola.rs/user-guide/concepts/data-types/categoricals/#using-the-global-string-cache
 // Create enum-backed Series
+#[example(chapter = 3, id = 3, title = "Compare and append enum-backed Series", snapshot)]
 pub fn ex003() -> PolarsResult<Series>  {
     print_function!();
 
@@ -330,19 +336,73 @@ pub fn ex003() -> PolarsResult<Series>  {
 }
 
 
-//-----
+// Enum dtype derived from a Rust enum, instead of building the RevMapping by hand as above.
+// The categories sort in the order of the variants, and the column converts back to Vec<Month>.
+#[example(chapter = 3, id = 4, title = "Enum dtype derived from a Rust enum", snapshot)]
+pub fn ex004() -> PolarsResult<DataFrame> {
+    print_function!();
+
+    #[derive(Debug, Clone, Copy, PartialEq, PolarsEnum)]
+    enum Month {
+        January,
+        February,
+        March,
+        April,
+        May,
+        June,
+        July,
+        August,
+        September,
+        October,
+        November,
+        December,
+    }
+
+    let dtype = Month::enum_dtype();
+    println!("dtype ::: {:?}, categories {:?}", dtype, Month::NAMES);
+    hr3();
+
+    let months = [Month::December, Month::February, Month::March, Month::January, Month::November, Month::October, Month::December];
+    let series = Month::to_series("items", &months)?.sort(Default::default())?;
+    println!("series sorted via enum ::: {:?}", series);
+    hr3();
+
+    // Back to Rust, type-safe: a value that is not a month is an error, not a string
+    let sorted: Vec<Month> = Month::from_series(&series)?;
+    println!("sorted ::: {:?}", sorted);
+    assert_eq!(sorted.first(), Some(&Month::January));
+    hr3();
 
-pub fn run(flag: Option<&str>) {
-    println!("003 Enum examples");
-    println!("https://docs.pola.rs/user-guide/concepts/data-types/categoricals/#enum-data-type");
+    // As a field, the enum becomes an Enum column of the DataFrame
+    #[derive(Debug, IntoDataFrame, FromDataFrame)]
+    struct Sale {
+        month: Month,
+        amount: i32,
+    }
+    let sales = vec![
+        Sale { month: Month::March, amount: 120 },
+        Sale { month: Month::January, amount: 80 },
+        Sale { month: Month::February, amount: 95 },
+    ];
+    let df = to_dataframe(&sales)?.sort(["month"], Default::default())?;
+    println!("df sorted via enum ::: {:?}", df);
+    hr3();
+
+    let sales: Vec<Sale> = from_dataframe(&df)?;
+    println!("sales ::: {:?}", sales);
 
-    let result001 = ex001().unwrap();
-    hr2();
-    pause();
+    Ok(df)
+}
 
-    let result002 = ex002().unwrap();
-    hr2();
-    pause();
 
-    let result003 = ex003().unwrap();
+//-----
+
+inventory::submit! {
+    ChapterInfo {
+        id: 3,
+        name: "ex_003_enum",
+        title: "Enum",
+        url: "https://docs.pola.rs/user-guide/concepts/data-types/categoricals/#enum-data-type",
+        features: &["lazy", "dtype-categorical"],
+    }
 }
@@ -4,6 +4,8 @@ use polars_arrow::*;
 use chrono::NaiveDate;
 
 use crate::utilities::*;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 //--------------------
@@ -19,6 +21,7 @@ use crate::print_function;
 //     // Bounds from impl:
 //     T: AsRef<[i32]>,
 
+#[example(chapter = 4, id = 1, title = "Series")]
 pub fn ex001() -> PolarsResult<Series> {
     let s = Series::new("a", &[1, 2, 3, 4, 5]);
 
@@ -32,6 +35,7 @@ pub fn ex001() -> PolarsResult<Series> {
 // DataFrame
 // https://docs.pola.rs/user-guide/concepts/data-structures/#dataframe
 
+#[example(chapter = 4, id = 2, title = "DataFrame")]
 pub fn ex002() -> PolarsResult<DataFrame> {
     let df: DataFrame = df!(
         "integer" => &[1,2,3,4,5],
@@ -61,6 +65,7 @@ pub fn ex002() -> PolarsResult<DataFrame> {
 
 // Viewing data
 // https://docs.pola.rs/user-guide/concepts/data-structures/#viewing-data
+#[example(chapter = 4, id = 3, title = "Viewing data with head, tail and sample", depends_on = "ex002")]
 pub fn ex003(df: DataFrame) -> PolarsResult<DataFrame> {
 
     // Head
@@ -85,19 +90,12 @@ pub fn ex003(df: DataFrame) -> PolarsResult<DataFrame> {
 
 //-----
 
-pub fn run(flag: Option<&str>) {
-    println!("004 Data Structure examples");
-    println!("https://docs.pola.rs/user-guide/concepts/data-structures/");
-
-
-    // data structure set: ex_004_data_structures
-    let result001 = ex001().unwrap(); // Create sample Series for examples
-    hr2();
-    pause();
-
-    let result002 = ex002().unwrap();
-    hr2();
-    pause();
-
-    let result003 = ex003(result002).unwrap();
+inventory::submit! {
+    ChapterInfo {
+        id: 4,
+        name: "ex_004_data_structures",
+        title: "Data Structure",
+        url: "https://docs.pola.rs/user-guide/concepts/data-structures/",
+        features: &[],
+    }
 }
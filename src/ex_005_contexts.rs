@@ -1,13 +1,16 @@
 use polars::prelude::*;
 use rand::{thread_rng, Rng};
 use crate::utilities::*;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 //-----
 
 // Create sample DaraFrame for examples
+#[example(chapter = 5, id = 1, title = "Create sample DataFrame")]
 pub fn ex001() -> PolarsResult<DataFrame> {
-    let mut arr = [064; 5];
+    let mut arr = [64; 5];
     thread_rng().fill(&mut arr);
 
     let df = df!(
@@ -28,6 +31,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 // Selection
 // https://docs.pola.rs/user-guide/concepts/contexts/#selection
 
+#[example(chapter = 5, id = 2, title = "Selection", depends_on = "ex001")]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
             .clone()
@@ -48,6 +52,7 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
 }
 
 // with_columns
+#[example(chapter = 5, id = 3, title = "with_columns", depends_on = "ex001")]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
                 .clone()
@@ -67,6 +72,7 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 // Filtering
 // https://docs.pola.rs/user-guide/concepts/contexts/#filtering
+#[example(chapter = 5, id = 4, title = "Filtering", depends_on = "ex001")]
 pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
                 .clone()
@@ -87,6 +93,7 @@ pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
 // Group by / aggregation
 // https://docs.pola.rs/user-guide/concepts/contexts/#group-by-aggregation
 
+#[example(chapter = 5, id = 5, title = "Group by / aggregation", depends_on = "ex001")]
 pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df.clone()
                 .lazy()
@@ -112,25 +119,12 @@ pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 //-----
 
-pub fn run(flag: Option<&str>) {
-    println!("005 Context examples");
-    println!("https://docs.pola.rs/user-guide/concepts/contexts/");
-
-    let df1 = ex001().unwrap();
-    hr2();
-    pause();
-
-    let result002 = ex002(&df1); //.unwrap();
-    hr2();
-    pause();
-
-    let result3 = ex003(&df1); //.unwrap();
-    hr2();
-    pause();
-
-    let result4 = ex004(&df1); //.unwrap();
-    hr2();
-    pause();
-
-    let result5 = ex005(&df1).unwrap();
+inventory::submit! {
+    ChapterInfo {
+        id: 5,
+        name: "ex_005_contexts",
+        title: "Context",
+        url: "https://docs.pola.rs/user-guide/concepts/contexts/",
+        features: &["lazy"],
+    }
 }
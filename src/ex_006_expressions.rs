@@ -1,7 +1,10 @@
 use polars::prelude::*;
 use crate::utilities::*;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
+#[example(chapter = 6, id = 1, title = "Create sample DataFrame", snapshot)]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let df = df!(
         "foo" => 0..10,
@@ -15,6 +18,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
     Ok(df)
 }
 
+#[example(chapter = 6, id = 2, title = "Sort and head inside select", depends_on = "ex001", snapshot)]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     // For more complex operations, try converting to a LazyFrame first
     let df_out = df.clone().lazy()
@@ -58,6 +62,7 @@ It's free until you modify it - only then will the data be copied
 */
 
 // Run two expressions via df.select
+#[example(chapter = 6, id = 3, title = "Run two expressions via select", depends_on = "ex001", snapshot)]
 fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
     //let df_out = df.clone().lazy().collect()?;
@@ -82,18 +87,12 @@ fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 //-----
 
-pub fn run(flag: Option<&str>) {
-    println!("006 Expression examples");
-    println!("https://docs.pola.rs/user-guide/concepts/expressions/");
-
-    let df = ex001().unwrap();
-    hr2();
-    pause();
-
-    let result02 = ex002(&df).unwrap();
-    hr2();
-    pause();
-
-    let result03 = ex003(&df).unwrap();
-
+inventory::submit! {
+    ChapterInfo {
+        id: 6,
+        name: "ex_006_expressions",
+        title: "Expression",
+        url: "https://docs.pola.rs/user-guide/concepts/expressions/",
+        features: &["lazy"],
+    }
 }
@@ -1,5 +1,8 @@
 use polars::prelude::*;
 use crate::utilities::*;
+use crate::datasets;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 //-----
@@ -15,12 +18,11 @@ use crate::print_function;
 
 
 // Eager API
+#[example(chapter = 7, id = 1, title = "Eager API", snapshot)]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let df = CsvReadOptions::default()
-        .try_into_reader_with_file_path(Some("src/iris.csv".into()))
-        .unwrap()
-        .finish()
-        .unwrap();
+        .try_into_reader_with_file_path(Some(datasets::path("iris")?))? // the bundled iris.csv
+        .finish()?;
     print_function!();
     println!("df: {:?}", df);
     hr3();
@@ -49,9 +51,10 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 
 
 // Lazy API
+#[example(chapter = 7, id = 2, title = "Lazy API")]
 pub fn ex002() -> PolarsResult<DataFrame> {
     // Define query
-    let q = LazyCsvReader::new("src/iris.csv")
+    let q = LazyCsvReader::new(datasets::path("iris")?) // the bundled iris.csv
         .with_has_header(true)
         .finish()?
         .filter(col("sepal_length").gt(lit(5)))
@@ -72,15 +75,12 @@ pub fn ex002() -> PolarsResult<DataFrame> {
 
 //-----
 
-pub fn run(flag: Option<&str>) {
-    println!("007 Lazy / Eager API examples");
-    println!("https://docs.pola.rs/user-guide/concepts/lazy-vs-eager/");
-    println!();
-
-    let result01 = ex001().unwrap();
-    hr2();
-    pause();
-
-    let result02 = ex002().unwrap();
-    hr2();
+inventory::submit! {
+    ChapterInfo {
+        id: 7,
+        name: "ex_007_lazy_eager_api",
+        title: "Lazy / Eager API",
+        url: "https://docs.pola.rs/user-guide/concepts/lazy-vs-eager/",
+        features: &["lazy"],
+    }
 }
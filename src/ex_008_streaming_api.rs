@@ -1,5 +1,8 @@
 use polars::prelude::*;
 use crate::utilities::*;
+use crate::datasets;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, explain};
 use crate::print_function;
 
 //-----
@@ -25,10 +28,9 @@ use crate::print_function;
 // }
 //-----
 
+#[example(chapter = 8, id = 1, title = "Collect a query in streaming mode", snapshot)]
 pub fn ex001() -> PolarsResult<DataFrame> {
-    let query = LazyCsvReader::new("src/iris.csv")
-        .with_has_header(true)
-        .finish()? // get the final LazyFrame
+    let query = datasets::load("iris")? // a LazyCsvReader over the bundled iris.csv
         .filter(col("sepal_length").gt(lit(5)))
         .group_by(vec![col("species")])
         .agg([
@@ -49,12 +51,14 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 }
 
 
+// #[explain] prints the plans of the query when it is collected.
+// The parts under STREAMING: run in streaming mode.
+#[example(chapter = 8, id = 2, title = "Explain which parts of a query stream", snapshot)]
+#[explain]
 pub fn ex002() -> PolarsResult<DataFrame> {
     print_function!();
 
-    let query = LazyCsvReader::new("src/iris.csv")
-        .with_has_header(true)
-        .finish()? // get the final LazyFrame
+    let query = datasets::load("iris")? // a LazyCsvReader over the bundled iris.csv
         .filter(col("sepal_length").gt(lit(5)))
         .group_by(vec![col("species")])
         .agg([
@@ -62,9 +66,8 @@ pub fn ex002() -> PolarsResult<DataFrame> {
         ])
         .sort(["sepal_width"], Default::default());
 
-    let query_plan = query.clone().with_streaming(true).explain(true)?;
     println!("To determine which parts of your query are streaming, use the explain method.");
-    println!("query_plan:\n{}", query_plan);
+    println!("Here #[explain] prints the plans before collecting:");
     hr3();
 
     let df = query.clone().with_streaming(true).collect()?;
@@ -77,11 +80,12 @@ pub fn ex002() -> PolarsResult<DataFrame> {
 
 
 
+#[example(chapter = 8, id = 3, title = "Query with non-streaming operations", snapshot)]
+#[explain]
 pub fn ex003() -> PolarsResult<DataFrame> {
     print_function!();
 
-    let query = LazyCsvReader::new("src/iris.csv")
-        .finish()?
+    let query = datasets::load("iris")?
         .with_columns(
             vec![col("sepal_length")
             .mean()
@@ -89,11 +93,9 @@ pub fn ex003() -> PolarsResult<DataFrame> {
             .alias("sepal_length_mean")
         ]);
 
-    let query_plan = query.clone().with_streaming(true).explain(true)?;
     println!();
     println!("Example with non-streaming operations, both .mean() and .over().");
     println!("This is because both operations require looking at the full dataset.");
-    println!("query_plan:\n{}", query_plan);
     hr3();
 
 
@@ -106,18 +108,12 @@ pub fn ex003() -> PolarsResult<DataFrame> {
 
 //-----
 
-pub fn run(flag: Option<&str>) {
-    println!("008 Streaming API examples");
-    println!("https://docs.pola.rs/user-guide/concepts/streaming/");
-    println!();
-
-    let result01 = ex001().unwrap();
-    hr2();
-    pause();
-
-    let result02 = ex002().unwrap();
-    hr2();
-    pause();
-
-    let result03 = ex003().unwrap();
+inventory::submit! {
+    ChapterInfo {
+        id: 8,
+        name: "ex_008_streaming_api",
+        title: "Streaming API",
+        url: "https://docs.pola.rs/user-guide/concepts/streaming/",
+        features: &["lazy", "streaming"],
+    }
 }
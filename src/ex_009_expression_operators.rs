@@ -1,10 +1,13 @@
 use polars::prelude::*;
 use crate::utilities::*;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 use rand::{thread_rng, Rng};
 
 // Create DataFrame for expression examples
+#[example(chapter = 9, id = 1, title = "Create sample DataFrame")]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let mut arr = [0f64; 5];
     thread_rng().fill(&mut arr);
@@ -25,6 +28,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 
 
 // Numerical expression
+#[example(chapter = 9, id = 2, title = "Numerical expressions", depends_on = "ex001")]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let df_numerical = df.clone().lazy()
         .select([
@@ -43,6 +47,7 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 
 // Logical expression
+#[example(chapter = 9, id = 3, title = "Logical expressions", depends_on = "ex001")]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let df_logical = df
         .clone()
@@ -69,22 +74,13 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 
 //-----
-pub fn run(flag: Option<&str>) {
-    println!("009 Expressions: Operators examples");
-    println!("https://docs.pola.rs/user-guide/expressions/operators/");
-    println!();
-
-    // Create DataFrame for examples
-    let df = ex001().unwrap();
-    hr2();
-    // pause();
-
-    // // Numerical
-    // let result02 = ex002(&df).unwrap();
-    // hr2();
-    // // pause();
-
-    // Logical
-    let result03 = ex003(&df).unwrap();
 
+inventory::submit! {
+    ChapterInfo {
+        id: 9,
+        name: "ex_009_expression_operators",
+        title: "Expressions: Operators",
+        url: "https://docs.pola.rs/user-guide/expressions/operators/",
+        features: &["lazy"],
+    }
 }
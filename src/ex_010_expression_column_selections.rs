@@ -1,18 +1,19 @@
 use polars::prelude::*;
 use crate::utilities::*;
-use crate::print_function;
-use my_proc_macro::print_source;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source};
 use chrono::prelude::*;
 use indoc::indoc;
 
 // Create DataFrame for examples
-#[print_source]
+#[example(chapter = 10, id = 1, title = "Create sample DataFrame", snapshot)]
+#[print_source(show_result)]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let df = df!(
         "id" => &[9, 4, 2],
         "place" => &["Mars", "Earth", "Saturn"],
         "date" => date_range(
-            "date".into(),
+            "date",
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(),
             NaiveDate::from_ymd_opt(2022, 1, 3).unwrap().and_hms_opt(0, 0, 0).unwrap(),
             Duration::parse("1d"),
@@ -21,7 +22,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
         "sales" => &[33.4, 2142134.1, 44.7],
         "has_people" => &[false, true, false],
         "logged_at" => date_range(
-            "logged_at".into(),
+            "logged_at",
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(),
             NaiveDate::from_ymd_opt(2022, 1, 1).unwrap().and_hms_opt(0, 0, 2).unwrap(),
             Duration::parse("1s"),
@@ -29,11 +30,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
             TimeUnit::Milliseconds,
             None)?,
         )?
-        .with_row_index("index".into(), None)?;
-
-    print_function!();
-    print_type(&df);
-    println!("{:?}", df);
+        .with_row_index("index", None)?;
 
     Ok(df)
 
@@ -41,7 +38,8 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 
 
 // API: all
-#[print_source]
+#[example(chapter = 10, id = 2, title = "Select all columns", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -51,16 +49,13 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 }
 
 
 // API: exclude
-#[print_source]
+#[example(chapter = 10, id = 3, title = "Exclude columns", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -70,16 +65,13 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 }
 
 // By multiple strings
 // Specifying multiple strings allows expressions to expand to all matching columns
-#[print_source]
+#[example(chapter = 10, id = 4, title = "Select by multiple names", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -89,16 +81,13 @@ pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 }
 
 
 // By regular expressions
-#[print_source]
+#[example(chapter = 10, id = 5, title = "Select by regular expression", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let notes = indoc! {r#"
 
@@ -122,18 +111,14 @@ pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 }
 
 
 // By data type
 // pl.col can select multiple columns using Polars data types.
-#[print_source]
+#[example(chapter = 10, id = 6, title = "Select by data type", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -147,10 +132,6 @@ fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 }
 
@@ -167,70 +148,12 @@ https://github.com/pola-rs/polars/blob/main/py-polars/polars/selectors.py
 
 //----------
 
-fn run_all() {
-    clear_screen();
-    let df = ex001().unwrap();
-    hr2();
-
-    let result02 = ex002(&df).unwrap();
-    hr2();
-
-    let result03 = ex003(&df).unwrap();
-    hr2();
-
-    let result04 = ex004(&df).unwrap();
-    hr2();
-
-    let result05 = ex005(&df).unwrap();
-    hr2();
-
-    let result = ex006(&df).unwrap();
-    hr2();
-}
-
-fn run_individually() {
-
-    let df = ex001().unwrap();
-    hr2();
-    pause();
-
-    clear_screen();
-    let result02 = ex002(&df).unwrap();
-    hr2();
-    pause();
-
-    clear_screen();
-    let result03 = ex003(&df).unwrap();
-    hr2();
-    pause();
-
-    clear_screen();
-    let result04 = ex004(&df).unwrap();
-    hr2();
-    pause();
-
-    clear_screen();
-    let result05 = ex005(&df).unwrap();
-    hr2();
-    pause();
-
-    clear_screen();
-    let result = ex006(&df).unwrap();
-    hr2();
-}
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    clear_screen();
-    println!("010 Expressions: Column Selection examples");
-    println!("https://docs.pola.rs/user-guide/expressions/column-selections/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.");
-        run_individually();
-    } else {
-        println!("Running all examples.");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 10,
+        name: "ex_010_expression_column_selections",
+        title: "Expressions: Column Selection",
+        url: "https://docs.pola.rs/user-guide/expressions/column-selections/",
+        features: &["lazy", "regex"],
     }
 }
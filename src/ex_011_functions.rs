@@ -1,5 +1,7 @@
 use polars::prelude::*;
 use crate::utilities::*;
+use crate::registry::ChapterInfo;
+use my_proc_macro::example;
 use crate::print_function;
 
 use rand::{thread_rng, Rng};
@@ -10,6 +12,7 @@ pub fn placeholder() {
 }
 
 // Create DataFrame for examples
+#[example(chapter = 11, id = 1, title = "Create sample DataFrame")]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let mut arr = [0f64; 5];
     thread_rng().fill(&mut arr);
@@ -30,6 +33,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 
 
 // Column naming
+#[example(chapter = 11, id = 2, title = "Column naming", depends_on = "ex001")]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let df_samename = df
         .clone()
@@ -46,6 +50,7 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
 }
 
 // Failing query
+#[example(chapter = 11, id = 3, title = "Failing query with duplicate output names", depends_on = "ex001", expect_error)]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     print_function!();
     let df_samename2 = df
@@ -69,6 +74,7 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 
 // Passing query via changing the output name with alias()
+#[example(chapter = 11, id = 4, title = "Rename outputs with alias", depends_on = "ex001")]
 pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let df_alias = df
         .clone()
@@ -90,6 +96,7 @@ pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
 // .n_unique() give the number of unique rows (as a scalar)
 // .approx_n_unique() is deprecated and removed:
 // https://github.com/pola-rs/polars/issues/13498
+#[example(chapter = 11, id = 5, title = "Count unique values", depends_on = "ex001")]
 pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let df_alias = df
         .clone()
@@ -115,6 +122,7 @@ in the when clause and when this evaluates to true the then
 expression is applied otherwise the otherwise expression is
 applied (row-wise).
 */
+#[example(chapter = 11, id = 6, title = "Conditionals with when / then / otherwise", depends_on = "ex001")]
 pub fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
     let df_conditional = df
         .clone()
@@ -138,80 +146,12 @@ pub fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 //----------
 
-fn run_all() {
-    // Sample DataFrame for examples
-    let df = ex001().unwrap();
-    hr2();
-    pause();
-
-    // Column naming
-    let result02 = ex002(&df).unwrap();
-    hr2();
-    pause();
-
-    // Failing query
-    let result03 = ex003(&df);
-    hr2();
-    pause();
-
-    // Passing query
-    let result04 = ex004(&df).unwrap();
-    hr2();
-    pause();
-
-    // Count unique values
-    let result05 = ex005(&df).unwrap();
-    hr2();
-    pause();
-
-    // Conditionals
-    let result06 = ex006(&df).unwrap();
-
-}
-
-fn run_individually() {
-    // Sample DataFrame for examples
-    let df = ex001().unwrap();
-    hr2();
-    pause();
-
-    // Column naming
-    let result02 = ex002(&df).unwrap();
-    hr2();
-    pause();
-
-    // Failing query
-    let result03 = ex003(&df);
-    hr2();
-    pause();
-
-    // Passing query
-    let result04 = ex004(&df).unwrap();
-    hr2();
-    pause();
-
-    // Count unique values
-    let result05 = ex005(&df).unwrap();
-    hr2();
-    pause();
-
-    // Conditionals
-    let result06 = ex006(&df).unwrap();
-
-}
-
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("011 Functions examples");
-    println!("https://docs.pola.rs/user-guide/expressions/functions/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 11,
+        name: "ex_011_functions",
+        title: "Functions",
+        url: "https://docs.pola.rs/user-guide/expressions/functions/",
+        features: &["lazy"],
     }
 }
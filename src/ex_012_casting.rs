@@ -1,13 +1,14 @@
 use polars::prelude::*;
 use crate::utilities::*;
-use crate::print_function;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source};
 use chrono::prelude::*;
 use indoc::indoc;
-use my_proc_macro::print_source;
 
 //---------------
 
-#[print_source]
+#[example(chapter = 12, id = 1, title = "Create sample DataFrame", snapshot)]
+#[print_source(show_result)]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let df = df!(
         "integers" => &[1,2,3,4,5],
@@ -15,15 +16,13 @@ pub fn ex001() -> PolarsResult<DataFrame> {
         "floats" => &[4.0, 5.0, 6.0, 7.0, 8.0],
         "floats_with_decimal" => &[4.532, 5.5, 6.5, 7.5, 8.5],
     )?;
-    print_function!();
-    print_type(&df);
-    println!("{:?}", df);
     Ok(df)
 }
 
 
 // Perform casting operations between floats and integers
-#[print_source]
+#[example(chapter = 12, id = 2, title = "Cast between floats and integers", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -41,10 +40,6 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 
 }
@@ -52,7 +47,8 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 // Downcast
 // Casting from Int64 to Int16 and Float64 to Float32 to reduce memory usage
-#[print_source]
+#[example(chapter = 12, id = 3, title = "Downcast to reduce memory usage", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -65,23 +61,15 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
                 .cast(DataType::Float32)
                 .alias("floats_smallfootprint")
         ])
-        .collect();
-
-    print_function!();
-    print_type(&out);
-
-    match &out {
-        Ok(out) => println!("{:?}", out),
-        Err(e) => println!("{:?}", e)
-    }
-
-    out
+        .collect()?;
 
+    Ok(out)
 }
 
 
 // Overflow
-#[print_source]
+#[example(chapter = 12, id = 4, title = "Overflow with a strict cast", depends_on = "ex001", expect_error)]
+#[print_source(show_result)]
 pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -90,25 +78,17 @@ pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
             col("big_integers")
                 .strict_cast(DataType::Int8)
         ])
-        .collect();
-
-    print_function!();
-    print_type(&out);
-    println!();
-
-    match &out {
-        Ok(out) => println!("{:?}", out),
-        Err(e) => println!("{:?}", e)
-    }
+        .collect()?;
 
-    out
+    Ok(out)
 }
 
 
 // You can also set the strict parameter to false,
 // which converts values that are overflowing to null values.
 // Overflow with strict parameter to false
-#[print_source]
+#[example(chapter = 12, id = 5, title = "Overflow with a non-strict cast", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -117,24 +97,16 @@ pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
             col("big_integers")
                 .cast(DataType::Int8)
         ])
-        .collect();
-
-    print_function!();
-    print_type(&out);
-    println!();
-
-    match &out {
-        Ok(out) => println!("{:?}", out),
-        Err(e) => println!("{:?}", e)
-    }
+        .collect()?;
 
-    out
+    Ok(out)
 }
 
 
 // Strings
 // Strings can be casted to numerical data types and vice versa.
-#[print_source]
+#[example(chapter = 12, id = 6, title = "Cast between strings and numbers", snapshot)]
+#[print_source(show_result)]
 pub fn ex006() -> PolarsResult<DataFrame> {
     let df = df!(
         "integers" => &[1, 2, 3, 4, 5],
@@ -152,9 +124,6 @@ pub fn ex006() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
     Ok(out)
 }
 
@@ -162,7 +131,8 @@ pub fn ex006() -> PolarsResult<DataFrame> {
 // In case the column contains a non-numerical value, Polars will throw
 // a ComputeError detailing the conversion error.
 // Setting strict=False will convert the non float value to null.
-#[print_source]
+#[example(chapter = 12, id = 7, title = "Non-strict cast of non-numerical strings", snapshot)]
+#[print_source(show_result)]
 pub fn ex007() -> PolarsResult<DataFrame> {
     let df = df! (
         "strings_not_float"=> ["4.0", "not_a_number", "6.0", "7.0", "8.0"]
@@ -175,25 +145,17 @@ pub fn ex007() -> PolarsResult<DataFrame> {
             //col("strings_not_float").strict_cast(DataType::Float64).alias("strict_cast"),
             col("strings_not_float").cast(DataType::Float64).alias("cast(strict=False)"),
         ])
-        .collect();
-
-    match &out {
-        Ok(out) => println!("{:?}", out),
-        Err(e) => println!("{:?}", e)
-     }
-
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
+        .collect()?;
 
-    out
+    Ok(out)
 }
 
 
 // Booleans
 // It's possible to perform casting operations between a numerical DataType and a boolean, and vice versa.
 // However, casting from a string (String) to a boolean is not permitted.
-#[print_source]
+#[example(chapter = 12, id = 8, title = "Cast between numbers and booleans", snapshot)]
+#[print_source(show_result)]
 pub fn ex008() -> PolarsResult<DataFrame> {
     let df = df! (
         "integers"=> &[-1, 0, 2, 3, 4],
@@ -217,10 +179,6 @@ pub fn ex008() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 }
 
@@ -231,10 +189,11 @@ Temporal data types such as Date or Datetime are represented as the number
 of days (Date) and microseconds (Datetime) since epoch. Therefore, casting
 between the numerical types and the temporal data types is allowed.
 */
-#[print_source]
+#[example(chapter = 12, id = 9, title = "Cast dates to integers", snapshot)]
+#[print_source(show_result)]
 pub fn ex009() -> PolarsResult<DataFrame> {
     let date = polars::time::date_range(
-        "date".into(),
+        "date",
         NaiveDate::from_ymd_opt(2022, 1, 1)
             .unwrap()
             .and_hms_opt(0, 0, 0)
@@ -251,7 +210,7 @@ pub fn ex009() -> PolarsResult<DataFrame> {
     .cast(&DataType::Date)?;
 
     let datetime = polars::time::date_range(
-        "datetime".into(),
+        "datetime",
         NaiveDate::from_ymd_opt(2022, 1, 1)
             .unwrap()
             .and_hms_opt(0, 0, 0)
@@ -286,10 +245,6 @@ pub fn ex009() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 
 }
@@ -301,10 +256,11 @@ pub fn ex009() -> PolarsResult<DataFrame> {
 // Cargo.toml:
 // [dependencies]
 // polars = { version = "...", features = ["lazy", "strings"] }
-#[print_source]
+#[example(chapter = 12, id = 10, title = "Convert between strings and dates", snapshot)]
+#[print_source(show_result)]
 pub fn ex010() -> PolarsResult<DataFrame> {
     let date = polars::time::date_range(
-        "date".into(),
+        "date",
         NaiveDate::from_ymd_opt(2022, 1, 1)
             .unwrap()
             .and_hms_opt(0,0,0)
@@ -344,128 +300,17 @@ pub fn ex010() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_type(&out);
-    println!("{:?}", out);
-
     Ok(out)
 
 }
 //----------
 
-fn run_all() {
-    // Sample DataFrame for examples
-    hr2();
-    let df = ex001().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result02 = ex002(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result03 = ex003(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result04 = ex004(&df);
-    pause();
-
-    clear_screen();
-    hr2();
-    let result05 = ex005(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result06 = ex006().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result07 = ex007().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result08 = ex008().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result09 = ex009().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result10 = ex010().unwrap();
-}
-
-fn run_individually() {
-    // Sample DataFrame for examples
-    hr2();
-    let df = ex001().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result02 = ex002(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result03 = ex003(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result04 = ex004(&df);
-    pause();
-
-    clear_screen();
-    hr2();
-    let result05 = ex005(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result06 = ex006().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result07 = ex007().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result08 = ex008().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result09 = ex009().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result10 = ex010().unwrap();
-}
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("012 Casting examples");
-    println!("https://docs.pola.rs/user-guide/expressions/casting/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 12,
+        name: "ex_012_casting",
+        title: "Casting",
+        url: "https://docs.pola.rs/user-guide/expressions/casting/",
+        features: &["lazy", "strings"],
     }
 }
@@ -1,26 +1,26 @@
 use polars::prelude::*;
 use crate::utilities::*;
-use crate::print_function;
-use my_proc_macro::print_source;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source};
 
 //---------------
 
 // Sample DataFrame for examples
 // Using the string namespace via .column attribute `str`
-#[print_source]
+#[example(chapter = 13, id = 1, title = "Create sample DataFrame", snapshot)]
+#[print_source(show_result)]
 pub fn ex001() -> PolarsResult<DataFrame> {
     let df = df!(
         "animal" => &[Some("Crab"), Some("cat and dog"), Some("rab$bit"), None],
     )?;
 
-    print_function!();
-    print_data(&df);
     Ok(df)
 }
 
 
 // len_bytes and len_chars
-#[print_source]
+#[example(chapter = 13, id = 2, title = "len_bytes and len_chars", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -31,14 +31,13 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 
 // String parsing
-#[print_source]
+#[example(chapter = 13, id = 3, title = "String parsing", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
 
     let out = df
@@ -65,14 +64,13 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 
 // Extract a pattern
-#[print_source]
+#[example(chapter = 13, id = 4, title = "Extract a pattern", snapshot)]
+#[print_source(show_result)]
 pub fn ex004() -> PolarsResult<DataFrame> {
     let df = df!(
         "a" => &[
@@ -92,9 +90,6 @@ pub fn ex004() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
-
     Ok(out)
 }
 
@@ -102,7 +97,8 @@ pub fn ex004() -> PolarsResult<DataFrame> {
 // Extract all occurrences of a pattern within a string with .extract_all()
 // Here, extract all numbers from a string using regex,
 // using the regex pattern (\d+), which matches one or more digits.
-#[print_source]
+#[example(chapter = 13, id = 5, title = "Extract all occurrences of a pattern", snapshot)]
+#[print_source(show_result)]
 pub fn ex005() -> PolarsResult<DataFrame> {
     let df = df!(
         "foo" => &["123 bla 45 asd", "xyz 678 910t"]
@@ -119,14 +115,13 @@ pub fn ex005() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 
 // Replace a pattern
-#[print_source]
+#[example(chapter = 13, id = 6, title = "Replace a pattern", snapshot)]
+#[print_source(show_result)]
 pub fn ex006() -> PolarsResult<DataFrame> {
     let df = df!(
         "id" => &[1, 2],
@@ -157,97 +152,17 @@ pub fn ex006() -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
-
     Ok(out)
 }
 
 //----------
 
-fn run_all() {
-    // Sample DataFrame for examples
-    hr2();
-    let df = ex001().unwrap();
-    pause();
-
-    // len_bytes and len_chars
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df).unwrap();
-    pause();
-
-    // String parsing
-    clear_screen();
-    hr2();
-    let result003 = ex003(&df).unwrap();
-    pause();
-
-    // Extract a pattern
-    clear_screen();
-    hr2();
-    let result004 = ex004().unwrap();
-    pause();
-
-    // Extract all occurrences of pattern
-    clear_screen();
-    hr2();
-    let result005 = ex005().unwrap();
-    pause();
-
-    // Replace a pattern
-    clear_screen();
-    hr2();
-    let result006 = ex006().unwrap();
-}
-
-fn run_individually() {
-    // Sample DataFrame for examples
-    hr2();
-    let df = ex001().unwrap();
-    pause();
-
-    // len_bytes and len_chars
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df).unwrap();
-    pause();
-
-    // String parsing
-    clear_screen();
-    hr2();
-    let result003 = ex003(&df).unwrap();
-    pause();
-
-    // Extract a pattern
-    clear_screen();
-    hr2();
-    let result004 = ex004().unwrap();
-    pause();
-
-    // Extract all occurrences of pattern
-    clear_screen();
-    hr2();
-    let result005 = ex005().unwrap();
-    pause();
-
-    // Replace a pattern
-    clear_screen();
-    hr2();
-    let result006 = ex006().unwrap();
-}
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("013 String examples");
-    println!("https://docs.pola.rs/user-guide/expressions/strings/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 13,
+        name: "ex_013_strings",
+        title: "String",
+        url: "https://docs.pola.rs/user-guide/expressions/strings/",
+        features: &["lazy", "strings", "regex"],
     }
 }
@@ -1,66 +1,20 @@
 use crate::print_function;
 use crate::utilities::*;
-use my_proc_macro::print_source;
+use crate::convert::from_dataframe;
+use crate::datasets;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source, FromDataFrame};
 use polars::prelude::*;
 
-use std::io::Cursor;
-
 //---------------
 
-use std::fs::File;
-use std::io::copy;
-use std::path::Path;
-
-use reqwest::blocking::Client;
-
-// utility function download_file defined in utilities
-#[print_source]
+// The dataset is downloaded on first use, see datasets.rs
+#[example(chapter = 14, id = 1, title = "Load the legislators dataset")]
+#[print_source(show_result)]
 pub fn ex001() -> PolarsResult<DataFrame> {
-    let url = "https://theunitedstates.io/congress-legislators/legislators-historical.csv";
-    let output_path = "./src/ex_014_aggregation_legislators-historical.csv";
-
-    match download_file(url, output_path) {
-        Ok(_) => println!("Process completed for file: {:?}\n", output_path),
-        Err(e) => eprintln!("Error: {:?}\n", e),
-    }
-
-    let mut data: Vec<u8> = Client::new()
-        .get(url)
-        .send()
-        .map_err(|e| PolarsError::ComputeError(format!("Failed to send request: {}", e).into()))?
-        .text()
-        .map_err(|e| PolarsError::ComputeError(format!("Failed to get text: {}", e).into()))?
-        .bytes()
-        .collect();
-
-    let mut dataset = CsvReadOptions::default()
-        .with_has_header(true)
-        .with_infer_schema_length(Some(100))
-        .map_parse_options(|parse_options| parse_options.with_try_parse_dates(true))
-        .try_into_reader_with_file_path(Some(output_path.into()))?
-        .finish()?;
-
-    // Modify specific columns to categorical
-    let categorical_columns = [
-        "first_name",
-        "last_name",
-        "gender",
-        "type",
-        "state",
-        "party",
-    ];
-    for col in categorical_columns.iter() {
-        if let Ok(series) = dataset.column(col) {
-            let categorical = series.cast(&DataType::Categorical(None, Default::default()))?;
-            dataset.replace(col, categorical)?;
-        }
-    }
-
-    // Convert birthday column to Date type if it exists
-    if let Ok(series) = dataset.column("birthday") {
-        let date_series = series.cast(&DataType::Date)?;
-        dataset.replace("birthday", date_series)?;
-    }
+    // The name columns, gender, type, state and party are read as Categorical
+    // and birthday as Date, see the registry entry
+    let dataset = datasets::load("legislators-historical")?.collect()?;
 
     let reduced_df = dataset.select([
         "first_name",
@@ -72,8 +26,6 @@ pub fn ex001() -> PolarsResult<DataFrame> {
         "birthday",
     ])?;
 
-    print_function!();
-    print_data(&reduced_df);
     Ok(reduced_df)
 }
 
@@ -96,6 +48,7 @@ pub fn ex001() -> PolarsResult<DataFrame> {
 
 // Basic aggregations (group_by, agg)
 
+#[example(chapter = 14, id = 2, title = "Basic aggregations", depends_on = "ex001")]
 //#[print_source]
 pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
@@ -124,7 +77,8 @@ pub fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
 }
 
 // Conditionals
-#[print_source]
+#[example(chapter = 14, id = 3, title = "Conditionals", depends_on = "ex001")]
+#[print_source(show_result)]
 pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -145,13 +99,25 @@ pub fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
         .limit(5)
         .collect()?;
 
-    print_function!();
-    print_data(&out);
+    // The top 5 read back into Rust structs, eg to hand them to code that knows nothing of Polars.
+    // The counts are sums of booleans, u32 in Polars.
+    #[derive(FromDataFrame)]
+    struct StateCounts {
+        state: String, // categorical column
+        anti: u32,
+        pro: u32,
+    }
+    let top: Vec<StateCounts> = from_dataframe(&out)?;
+    for counts in &top {
+        println!("{}: {} pro-administration, {} anti-administration", counts.state, counts.pro, counts.anti);
+    }
+
     Ok(out)
 }
 
 // nested group by
-#[print_source]
+#[example(chapter = 14, id = 4, title = "Nested group by", depends_on = "ex001")]
+#[print_source(show_result)]
 pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -172,13 +138,12 @@ pub fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
         .limit(5)
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 // Filtering groups
-#[print_source]
+#[example(chapter = 14, id = 5, title = "Filtering groups", depends_on = "ex001")]
+#[print_source(show_result)]
 pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     fn compute_age() -> Expr {
         lit(2022) - col("birthday").dt().year()
@@ -208,14 +173,12 @@ pub fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
         .limit(5)
         .collect()?;
 
-    print_function!();
-    print_data(&out);
-
     Ok(out)
 }
 
 // Sorting
-#[print_source]
+#[example(chapter = 14, id = 6, title = "Sorting", depends_on = "ex001")]
+#[print_source(show_result)]
 pub fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
     fn get_person() -> Expr {
         col("first_name") + lit(" ") + col("last_name")
@@ -240,14 +203,12 @@ pub fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
         .limit(5)
         .collect()?;
 
-    print_function!();
-    print_data(&out);
-
     Ok(out)
 }
 
 // Sorting names alphabetically, in group_by context separate from the DataFrame.
-#[print_source]
+#[example(chapter = 14, id = 7, title = "Sort names alphabetically within groups", depends_on = "ex001")]
+#[print_source(show_result)]
 pub fn ex007(df: &DataFrame) -> PolarsResult<DataFrame> {
     fn get_person() -> Expr {
         col("first_name") + lit(" ") + col("last_name")
@@ -274,9 +235,6 @@ pub fn ex007(df: &DataFrame) -> PolarsResult<DataFrame> {
         .limit(5)
         .collect()?;
 
-    print_function!();
-    print_data(&out);
-
     Ok(out)
 }
 
@@ -288,7 +246,8 @@ fn get_person() -> Expr {
 // Sort by another column in the group_by context.
 // If we want to know if the alphabetically sorted name is male or female we could add:
 // pl.col("gender").sort_by(get_person()).first()
-#[print_source]
+#[example(chapter = 14, id = 8, title = "Sort by another column within groups", depends_on = "ex001")]
+#[print_source(show_result)]
 pub fn ex008(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -316,119 +275,17 @@ pub fn ex008(df: &DataFrame) -> PolarsResult<DataFrame> {
         .limit(5)
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 //----------
 
-fn run_all() {
-    // Sample DataFrame for examples
-    hr2();
-    let df = ex001().unwrap();
-    //pause();
-
-    // Basic aggregations
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df).unwrap();
-    pause();
-
-    // Conditionals
-    clear_screen();
-    hr2();
-    let result = ex003(&df).unwrap();
-    pause();
-
-    // Nested group by
-    clear_screen();
-    hr2();
-    let result = ex004(&df).unwrap();
-    pause();
-
-    // Nested group by
-    clear_screen();
-    hr2();
-    let result = ex005(&df).unwrap();
-    pause();
-
-    // Sorting
-    clear_screen();
-    hr2();
-    let result = ex006(&df).unwrap();
-    pause();
-
-    // Sorting 2
-    clear_screen();
-    hr2();
-    let result007 = ex007(&df).unwrap();
-
-    // Sorting 3
-    clear_screen();
-    hr2();
-    let result008 = ex008(&df).unwrap();
-}
-
-fn run_individually() {
-    // Sample DataFrame for examples
-    hr2();
-    let df = ex001().unwrap();
-    //pause();
-
-    // Basic aggregations
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df).unwrap();
-    pause();
-
-    // Conditionals
-    clear_screen();
-    hr2();
-    let result = ex003(&df).unwrap();
-    pause();
-
-    // Nested group by
-    clear_screen();
-    hr2();
-    let result = ex004(&df).unwrap();
-    pause();
-
-    // Nested group by
-    clear_screen();
-    hr2();
-    let result = ex005(&df).unwrap();
-    pause();
-
-    // Sorting
-    clear_screen();
-    hr2();
-    let result = ex006(&df).unwrap();
-    pause();
-
-    // Sorting 2
-    clear_screen();
-    hr2();
-    let result007 = ex007(&df).unwrap();
-
-    // Sorting 3
-    clear_screen();
-    hr2();
-    let result008 = ex008(&df).unwrap();
-}
-
-//----------
-
-
-pub fn run(flag: Option<&str>) {
-    println!("014 Aggregation examples");
-    println!("https://docs.pola.rs/user-guide/expressions/aggregation/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 14,
+        name: "ex_014_aggregation",
+        title: "Aggregation",
+        url: "https://docs.pola.rs/user-guide/expressions/aggregation/",
+        features: &["lazy", "dtype-categorical"],
     }
 }
@@ -1,47 +1,25 @@
-use crate::print_function;
 use crate::utilities::*;
-use my_proc_macro::print_source;
+use crate::datasets;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source};
 use polars::prelude::*;
-use reqwest::blocking::Client;
 
 //----------
 
 // Create sample DataFrame for following examples
-// download_file is a function defined in utility
-#[print_source]
+// The dataset is downloaded on first use, see datasets.rs
+#[example(chapter = 16, id = 1, title = "Load the pokemon dataset")]
+#[print_source(show_result)]
 pub fn ex001() -> PolarsResult<DataFrame> {
-    let url = "https://gist.githubusercontent.com/ritchie46/cac6b337ea52281aa23c049250a4ff03/raw/89a957ff3919d90e6ef2d34235e6bf22304f3366/pokemon.csv";
-    let output_path = "./src/ex_016_aggregation_pokemon.csv";
+    let dataset = datasets::load("pokemon")?.collect()?;
 
-    match download_file(url, output_path) {
-        Ok(_) => println!("Process completed for file: {:?}\n", output_path),
-        Err(e) => eprintln!("Error: {:?}\n", e),
-    }
-
-    let mut data: Vec<u8> = Client::new()
-        .get(url)
-        .send()
-        .map_err(|e| PolarsError::ComputeError(format!("Failed to send request: {}", e).into()))?
-        .text()
-        .map_err(|e| PolarsError::ComputeError(format!("Failed to get text: {}", e).into()))?
-        .bytes()
-        .collect();
-
-    let mut dataset = CsvReadOptions::default()
-        .with_has_header(true)
-        .with_infer_schema_length(Some(100))
-        .map_parse_options(|parse_options| parse_options.with_try_parse_dates(true))
-        .try_into_reader_with_file_path(Some(output_path.into()))?
-        .finish()?;
-
-    print_function!();
-    print_data(&dataset);
     Ok(dataset)
 }
 
 
 // Group by aggregations in selection
-#[print_source]
+#[example(chapter = 16, id = 2, title = "Group by aggregations in selection", depends_on = "ex001")]
+#[print_source(show_result)]
 fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -65,14 +43,13 @@ fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 // Operations per group
 // Create a filtered sample DataFrame for following examples
-#[print_source]
+#[example(chapter = 16, id = 3, title = "Filter the Psychic pokemon", depends_on = "ex001")]
+#[print_source(show_result)]
 fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let filtered = df
         .clone()
@@ -87,15 +64,14 @@ fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&filtered);
     Ok(filtered)
 
 }
 
 
 // Sort column "Speed" in descending order, via window functions
-#[print_source]
+#[example(chapter = 16, id = 4, title = "Sort by speed within each type", depends_on = "ex003")]
+#[print_source(show_result)]
 fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -110,8 +86,6 @@ fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
@@ -121,7 +95,8 @@ fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
 // sort the pokemon within a type by speed in descending order and select the first 3 as "fastest/group"
 // sort the pokemon within a type by attack in descending order and select the first 3 as "strongest/group"
 // sort the pokemon within a type by name and select the first 3 as "sorted_by_alphabet"
-#[print_source]
+#[example(chapter = 16, id = 5, title = "First three pokemon per type", depends_on = "ex001")]
+#[print_source(show_result)]
 fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -190,68 +165,17 @@ fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
 
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 //----------
 
-fn run_all() {
-    clear_screen();
-    hr2();
-    let df = ex001().unwrap();
-
-    hr2();
-    let result002 = ex002(&df).unwrap();
-
-    hr2();
-    let df_filtered = ex003(&df).unwrap();
-
-    hr2();
-    let result004 = ex004(&df_filtered).unwrap();
-
-    hr2();
-    let result005 = ex005(&df).unwrap();
-}
-
-fn run_individually() {
-    clear_screen();
-    hr2();
-    let df = ex001().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let df_filtered = ex003(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result004 = ex004(&df_filtered).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result005 = ex005(&df).unwrap();
-}
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("016 Window functions examples");
-    println!("https://docs.pola.rs/user-guide/expressions/window//");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 16,
+        name: "ex_016_window",
+        title: "Window functions",
+        url: "https://docs.pola.rs/user-guide/expressions/window/",
+        features: &["lazy"],
     }
 }
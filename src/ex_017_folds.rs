@@ -1,6 +1,6 @@
-use crate::print_function;
 use crate::utilities::*;
-use my_proc_macro::print_source;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source};
 use polars::prelude::*;
 // use reqwest::blocking::Client;
 use indoc::indoc;
@@ -16,13 +16,18 @@ This snippet recursively applies the function f(acc, x) -> acc to an accumulator
 "#};
 
 // Manual sum
-#[print_source]
+#[example(chapter = 17, id = 1, title = "Manual sum", snapshot)]
+#[print_source(
+    title = "Manual sum",
+    url = "https://docs.pola.rs/user-guide/expressions/folds/",
+    notes = NOTES_EX001,
+    show_result = true,
+)]
 fn ex001() -> PolarsResult<DataFrame> {
     let df = df!(
         "a" => &[1, 2, 3],
         "b" => &[10, 20, 30],
     )?;
-    print_function!();
     print_data(&df);
 
     let out = df
@@ -36,9 +41,6 @@ fn ex001() -> PolarsResult<DataFrame> {
             ).alias("sum")
         ])
         .collect()?;
-
-    print_data(&out);
-    println!("\nNOTES\n{}", NOTES_EX001);
     Ok(out)
 }
 
@@ -59,7 +61,13 @@ where
     E: AsRef<[Expr]>,
 "#};
 
-#[print_source]
+#[example(chapter = 17, id = 2, title = "Conditional filter on all columns", snapshot)]
+#[print_source(
+    title = "Conditional filter on all columns",
+    url = "https://docs.pola.rs/user-guide/expressions/folds/",
+    notes = NOTES_EX002,
+    show_result = true,
+)]
 fn ex002() -> PolarsResult<DataFrame> {
     let df = df!(
         "a" => &[1, 2, 3],
@@ -77,10 +85,6 @@ fn ex002() -> PolarsResult<DataFrame> {
             )
         )
         .collect()?;
-
-    print_function!();
-    print_data(&out);
-    println!("\nNOTES\n{}", NOTES_EX002);
     Ok(out)
 }
 
@@ -95,13 +99,18 @@ Therefore, we recommend using the concat_str expression for this.
 .concat_str() need feature "concat_str" to be enabled in Cargo.toml
 "#};
 
-#[print_source]
+#[example(chapter = 17, id = 3, title = "Folds and string data", snapshot)]
+#[print_source(
+    title = "Folds and string data",
+    url = "https://docs.pola.rs/user-guide/expressions/folds/",
+    notes = NOTES_EX003,
+    show_result = true,
+)]
 fn ex003() -> PolarsResult<DataFrame> {
     let df = df!(
         "a" => &["a", "b", "c"],
         "b" => &[1, 2, 3],
     )?;
-    print_function!();
     print_data(&df);
 
     let out = df
@@ -111,9 +120,6 @@ fn ex003() -> PolarsResult<DataFrame> {
             concat_str([col("a"), col("b")], "", false)
         ])
         .collect()?;
-
-    print_data(&out);
-    println!("\nNOTES\n{}", NOTES_EX003);
     Ok(out)
 
 }
@@ -123,46 +129,12 @@ fn ex003() -> PolarsResult<DataFrame> {
 
 //----------
 
-fn run_all() {
-    clear_screen();
-    hr2();
-    let result001 = ex001().unwrap();
-
-    hr2();
-    let result002 = ex002().unwrap();
-
-    hr2();
-    let result003 = ex003().unwrap();
-}
-
-fn run_individually() {
-    clear_screen();
-    hr2();
-    let result001 = ex001().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result002 = ex002().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result003 = ex003().unwrap();
-    pause();
-}
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("017 Fold functions examples");
-    println!("https://docs.pola.rs/user-guide/expressions/folds/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 17,
+        name: "ex_017_folds",
+        title: "Fold functions",
+        url: "https://docs.pola.rs/user-guide/expressions/folds/",
+        features: &["lazy", "concat_str"],
     }
 }
@@ -1,6 +1,7 @@
 use crate::print_function;
 use crate::utilities::*;
-use my_proc_macro::print_source;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source};
 use polars::prelude::*;
 // use reqwest::blocking::Client;
 use indoc::indoc;
@@ -8,7 +9,8 @@ use indoc::indoc;
 //----------
 
 // Create sample DataFrame for examples
-#[print_source]
+#[example(chapter = 18, id = 1, title = "Create weather DataFrame", snapshot)]
+#[print_source(show_result)]
 fn ex001() -> PolarsResult<DataFrame> {
     let stns: Vec<String> = (1..6)
             .map(|i| format!("Station {i}"))
@@ -24,14 +26,13 @@ fn ex001() -> PolarsResult<DataFrame> {
         ]
     )?;
 
-    print_function!();
-    print_data(&weather);
     Ok(weather)
 }
 
 // Creating a List column
 // Extract individual data from 'temperatures' column with str().split()
-#[print_source]
+#[example(chapter = 18, id = 2, title = "Create a List column", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -41,14 +42,13 @@ fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 
 // Explore data into own row
-#[print_source]
+#[example(chapter = 18, id = 3, title = "Explode lists into rows", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -59,15 +59,14 @@ fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
         .explode(["temperatures"])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 
 // Operating on List columns
 // head, tail, slice, lenghts operations
-#[print_source]
+#[example(chapter = 18, id = 4, title = "head, slice and len on lists", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -93,8 +92,6 @@ fn ex004(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 
 }
@@ -132,7 +129,13 @@ Some methods provided by this trait include:
     join: Joins the elements of each list into a single string.
 "#};
 
-#[print_source]
+#[example(chapter = 18, id = 5, title = "Count parse errors with list eval", depends_on = "ex001", snapshot)]
+#[print_source(
+    title = "Count parse errors with list eval",
+    url = "https://docs.pola.rs/user-guide/expressions/lists/",
+    notes = NOTES_EX005,
+    show_result = true,
+)]
 fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -147,17 +150,14 @@ fn ex005(df: &DataFrame) -> PolarsResult<DataFrame> {
             .sum()
             .alias("errors")])
         .collect()?;
-
-    print_function!();
-    print_data(&out);
-    println!("\nNOTES\n{}", NOTES_EX005);
     Ok(out)
 }
 
 
 // Using .list().eval() with regex
 // Recognize the presence of any alphabetical character
-#[print_source]
+#[example(chapter = 18, id = 6, title = "Detect letters with a regex in list eval", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -184,8 +184,6 @@ fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
@@ -194,7 +192,8 @@ fn ex006(df: &DataFrame) -> PolarsResult<DataFrame> {
 // Ideal for computing in row orientation
 // We can apply any Polars operations on the elements of the list with the list.eval (list().eval in Rust) expression
 // New DataFrame for the following examples
-#[print_source]
+#[example(chapter = 18, id = 7, title = "Create weather by day DataFrame", snapshot)]
+#[print_source(show_result)]
 fn ex007() -> PolarsResult<DataFrame> {
     let stns: Vec<String> = (1..11)
             .map(|i| format!("Station {i}"))
@@ -207,8 +206,6 @@ fn ex007() -> PolarsResult<DataFrame> {
         "day_3" => &[16, 15, 24, 24, 8, 23, 19, 23, 16, 10],
     )?;
 
-    print_function!();
-    print_data(&weather_by_day);
     Ok(weather_by_day)
 }
 
@@ -233,7 +230,13 @@ Need to enable crate feature `round_series` in Cargo.toml, eg:
     polars = { version = "0.42.0", features = [ "round_series"]}
 
 "#};
-#[print_source]
+#[example(chapter = 18, id = 8, title = "Row-wise percentage rank", depends_on = "ex007", snapshot)]
+#[print_source(
+    title = "Row-wise percentage rank",
+    url = "https://docs.pola.rs/user-guide/expressions/lists/",
+    notes = NOTES_EX008,
+    show_result = true,
+)]
 fn ex008(df: &DataFrame) -> PolarsResult<DataFrame> {
     let rank_pct = (
         col("")
@@ -264,22 +267,19 @@ fn ex008(df: &DataFrame) -> PolarsResult<DataFrame> {
                 .alias("temps_rank"),
         ])
         .collect()?;
-
-    print_function!();
-    print_data(&out);
-    println!("\nNOTES\n{}", NOTES_EX008);
     Ok(out)
 
 }
 
 
 // Polars Arrays
+#[example(chapter = 18, id = 9, title = "Create Array columns", snapshot)]
 fn ex009() -> PolarsResult<DataFrame> {
-    let mut col1: ListPrimitiveChunkedBuilder<Int32Type> = ListPrimitiveChunkedBuilder::new("Array_1".into(), 8, 8, DataType::Int32);
+    let mut col1: ListPrimitiveChunkedBuilder<Int32Type> = ListPrimitiveChunkedBuilder::new("Array_1", 8, 8, DataType::Int32);
     col1.append_slice(&[1, 3]);
     col1.append_slice(&[2, 5]);
     let mut col2: ListPrimitiveChunkedBuilder<Int32Type> =
-        ListPrimitiveChunkedBuilder::new("Array_2".into(), 8, 8, DataType::Int32);
+        ListPrimitiveChunkedBuilder::new("Array_2", 8, 8, DataType::Int32);
     col2.append_slice(&[1, 7, 3]);
     col2.append_slice(&[8, 1, 0]);
 
@@ -295,7 +295,8 @@ fn ex009() -> PolarsResult<DataFrame> {
 
 
 // Running basic operations on Polars Arrays
-#[print_source]
+#[example(chapter = 18, id = 10, title = "Basic operations on arrays", depends_on = "ex009", snapshot)]
+#[print_source(show_result)]
 fn ex010(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -308,8 +309,6 @@ fn ex010(df: &DataFrame) -> PolarsResult<DataFrame> {
         ])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
@@ -317,104 +316,12 @@ fn ex010(df: &DataFrame) -> PolarsResult<DataFrame> {
 
 //----------
 
-fn run_all() {
-    clear_screen();
-    hr2();
-    let df = ex001().unwrap();
-
-    hr2();
-    let result002 = ex002(&df).unwrap();
-
-    hr2();
-    let result003 = ex003(&df).unwrap();
-
-    hr2();
-    let result004 = ex004(&df).unwrap();
-
-    hr2();
-    let result005 = ex005(&df).unwrap();
-
-    hr2();
-    let result006 = ex006(&df).unwrap();
-
-    hr2();
-    let df_weather = ex007().unwrap();
-
-    hr2();
-    let result008 = ex008(&df_weather).unwrap();
-
-    hr2();
-    let df_array = ex009().unwrap();
-
-    hr2();
-    let result010 = ex010(&df_array).unwrap();
-}
-
-fn run_individually() {
-    clear_screen();
-    hr2();
-    let df = ex001().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result003 = ex003(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result004 = ex004(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result005 = ex005(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result006 = ex006(&df).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let df_weather = ex007().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result008 = ex008(&df_weather).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let df_array = ex009().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result010 = ex010(&df_array).unwrap();
-    pause();
-
-
-}
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("018 List and Arrays examples");
-    println!("https://docs.pola.rs/user-guide/expressions/lists/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 18,
+        name: "ex_018_lists_arrays",
+        title: "List and Arrays",
+        url: "https://docs.pola.rs/user-guide/expressions/lists/",
+        features: &["lazy", "strings", "list_eval", "rank", "round_series"],
     }
 }
@@ -1,6 +1,7 @@
-use crate::print_function;
 use crate::utilities::*;
-use my_proc_macro::print_source;
+use crate::convert::to_dataframe;
+use crate::registry::ChapterInfo;
+use my_proc_macro::{example, print_source, IntoDataFrame};
 use polars::prelude::*;
 // use reqwest::blocking::Client;
 use indoc::indoc;
@@ -8,17 +9,37 @@ use indoc::indoc;
 //----------
 
 // Create sample DataFrame for following examples
-#[print_source]
+// One struct per rating rather than one array per column, converted by #[derive(IntoDataFrame)]
+#[example(chapter = 19, id = 1, title = "Create ratings DataFrame", snapshot)]
+#[print_source(show_result)]
 fn ex001() -> PolarsResult<DataFrame> {
-    let ratings = df!(
-        "Movie"=> &["Cars", "IT", "ET", "Cars", "Up", "IT", "Cars", "ET", "Up", "ET"],
-        "Theatre"=> &["NE", "ME", "IL", "ND", "NE", "SD", "NE", "IL", "IL", "SD"],
-        "Avg_Rating"=> &[4.5, 4.4, 4.6, 4.3, 4.8, 4.7, 4.7, 4.9, 4.7, 4.6],
-        "Count"=> &[30, 27, 26, 29, 31, 28, 28, 26, 33, 26],
-    )?;
-
-    print_function!();
-    print_data(&ratings);
+    #[derive(IntoDataFrame)]
+    struct Rating {
+        #[polars(rename = "Movie")]
+        movie: &'static str,
+        #[polars(rename = "Theatre")]
+        theatre: &'static str,
+        #[polars(rename = "Avg_Rating")]
+        avg_rating: f64,
+        #[polars(rename = "Count")]
+        count: i32,
+    }
+
+    let rating = |movie, theatre, avg_rating, count| Rating { movie, theatre, avg_rating, count };
+    let ratings = vec![
+        rating("Cars", "NE", 4.5, 30),
+        rating("IT", "ME", 4.4, 27),
+        rating("ET", "IL", 4.6, 26),
+        rating("Cars", "ND", 4.3, 29),
+        rating("Up", "NE", 4.8, 31),
+        rating("IT", "SD", 4.7, 28),
+        rating("Cars", "NE", 4.7, 28),
+        rating("ET", "IL", 4.9, 26),
+        rating("Up", "IL", 4.7, 33),
+        rating("ET", "SD", 4.6, 26),
+    ];
+    let ratings = to_dataframe(&ratings)?;
+
     Ok(ratings)
 }
 
@@ -36,7 +57,13 @@ Need to enable crate feature `dtype-struct` in Cargo.toml:
 polars = { version = "0.42.0", features = [ "dtype-struct"]}
 
 "#};
-#[print_source]
+#[example(chapter = 19, id = 2, title = "value_counts returns a Struct", depends_on = "ex001", snapshot)]
+#[print_source(
+    title = "value_counts returns a Struct",
+    url = "https://docs.pola.rs/user-guide/expressions/structs/",
+    notes = NOTES_EX002,
+    show_result = true,
+)]
 fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -51,17 +78,14 @@ fn ex002(df: &DataFrame) -> PolarsResult<DataFrame> {
                 )
         ])
         .collect()?;
-
-    print_function!();
-    print_data(&out);
-    println!("\nNOTES\n{}", NOTES_EX002);
     Ok(out)
 }
 
 
 // Unnest the given Struct columns.
 // The fields of the Struct type will be inserted as columns.
-#[print_source]
+#[example(chapter = 19, id = 3, title = "Unnest Struct columns", depends_on = "ex001", snapshot)]
+#[print_source(show_result)]
 fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
     let out = df
         .clone()
@@ -78,27 +102,23 @@ fn ex003(df: &DataFrame) -> PolarsResult<DataFrame> {
         .unnest(["Theatre"])
         .collect()?;
 
-    print_function!();
-    print_data(&out);
     Ok(out)
 }
 
 
 // Structs as dicts
 // Polars will interpret a dict sent to the Series constructor as a Struct
-#[print_source]
+#[example(chapter = 19, id = 4, title = "Struct Series from a DataFrame", snapshot)]
+#[print_source(show_result)]
 fn ex004() -> PolarsResult<Series> {
     let ratings_series = df!(
         "Movie" => &["Cars","Toy Story"],
         "Theatre" => &["NE", "ME"],
         "Avg_Rating" => &[4.5, 4.9],
     )?
-    .into_struct("ratings".into())
+    .into_struct("ratings")
     .into_series();
 
-    print_function!();
-    print_type(&ratings_series);
-    println!("DATA      : {:?}", &ratings_series);
     Ok(ratings_series)
 }
 
@@ -106,56 +126,12 @@ fn ex004() -> PolarsResult<Series> {
 
 //----------
 
-fn run_all() {
-    clear_screen();
-    hr2();
-    let df_ratings = ex001().unwrap();
-
-    hr2();
-    let result002 = ex002(&df_ratings).unwrap();
-
-    hr2();
-    let result003 = ex003(&df_ratings).unwrap();
-
-    hr2();
-    let ratings_series = ex004().unwrap();
-}
-
-fn run_individually() {
-    clear_screen();
-    hr2();
-    let df_ratings = ex001().unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result002 = ex002(&df_ratings).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let result003 = ex003(&df_ratings).unwrap();
-    pause();
-
-    clear_screen();
-    hr2();
-    let ratings_series = ex004().unwrap();
-    pause();
-
-}
-
-
-//----------
-
-pub fn run(flag: Option<&str>) {
-    println!("019 Struct datatype examples");
-    println!("https://docs.pola.rs/user-guide/expressions/structs/");
-
-    if let Some(arg) = flag {
-        println!("Running examples individually.\n");
-        run_individually();
-    } else {
-        println!("Running all examples.\n");
-        run_all();
+inventory::submit! {
+    ChapterInfo {
+        id: 19,
+        name: "ex_019_struct",
+        title: "Struct datatype",
+        url: "https://docs.pola.rs/user-guide/expressions/structs/",
+        features: &["lazy", "dtype-struct"],
     }
 }
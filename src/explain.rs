@@ -0,0 +1,68 @@
+use polars::prelude::*;
+
+use crate::utilities::*;
+
+// Query plans of the examples annotated with #[explain]. The attribute rewrites
+// every `.collect()` of the example into `.explained_collect()`: a LazyFrame prints
+// how Polars rewrote it before it runs, anything else, eg an iterator, is
+// collected as before.
+
+pub trait ExplainLazyFrame {
+    fn explained_collect(self) -> PolarsResult<DataFrame>;
+}
+
+impl ExplainLazyFrame for LazyFrame {
+    fn explained_collect(self) -> PolarsResult<DataFrame> {
+        if !printing() {
+            return self.collect();
+        }
+        let optimized = self.describe_optimized_plan()?;
+        println!("UNOPTIMIZED PLAN\n{}", self.describe_plan()?.trim_end());
+        println!("\nOPTIMIZED PLAN\n{}", optimized.trim_end());
+
+        let segments = streaming_segments(&optimized);
+        if segments > 0 {
+            println!("\nStreaming segments: {}", segments);
+        }
+        hr3();
+
+        self.collect()
+    }
+}
+
+// Parts of a query run by the streaming engine, with .with_streaming(true).
+// Matched on the plan text, the test below notices if Polars changes it.
+fn streaming_segments(optimized_plan: &str) -> usize {
+    optimized_plan.lines().filter(|line| line.trim() == "STREAMING:").count()
+}
+
+pub trait ExplainIterator: Iterator + Sized {
+    fn explained_collect<B: FromIterator<Self::Item>>(self) -> B {
+        self.collect()
+    }
+}
+
+impl<I: Iterator> ExplainIterator for I {}
+
+//--------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_queries_have_streaming_segments() {
+        let df = df!("group" => ["a", "b", "a"], "value" => [1, 2, 3]).unwrap();
+        let query = df
+            .lazy()
+            .filter(col("value").gt(lit(1)))
+            .group_by([col("group")])
+            .agg([col("value").sum()]);
+
+        let plan = query.clone().describe_optimized_plan().unwrap();
+        assert_eq!(streaming_segments(&plan), 0, "{}", plan);
+
+        let plan = query.with_streaming(true).describe_optimized_plan().unwrap();
+        assert!(streaming_segments(&plan) >= 1, "no STREAMING: line in the plan\n{}", plan);
+    }
+}
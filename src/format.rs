@@ -0,0 +1,189 @@
+use std::io::Write;
+
+use polars::prelude::*;
+use serde_json::{json, Map, Value};
+
+use crate::registry::Output;
+use crate::runner::ExampleReport;
+
+// How the frames returned by the examples are emitted
+// `Table` leaves the printing to the examples themselves, the other formats
+// silence the examples and write only the returned frames to stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,     // one array of {"chapter", "example", "data"} records
+    Ndjson,   // one record per line
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 5] = ["table", "json", "ndjson", "csv", "markdown"];
+
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "table" => Some(Format::Table),
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            "markdown" => Some(Format::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn is_structured(self) -> bool {
+        self != Format::Table
+    }
+}
+
+//--------------------
+
+// Write the frames returned by the reported examples.
+// Series are written as a one-column frame, examples returning nothing are left out.
+pub fn write_reports(reports: &[ExampleReport], format: Format, out: &mut impl Write) -> PolarsResult<()> {
+    let frames: Vec<(&ExampleReport, DataFrame)> = reports
+        .iter()
+        .filter_map(|report| match &report.output {
+            Some(Output::Frame(df)) => Some((report, df.clone())),
+            Some(Output::Series(s)) => Some((report, s.clone().into_frame())),
+            _ => None,
+        })
+        .collect();
+
+    match format {
+        Format::Table => {}
+        Format::Json => {
+            let records = frames
+                .iter()
+                .map(|(report, df)| record(report, df))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            serde_json::to_writer_pretty(&mut *out, &records).map_err(to_polars)?;
+            writeln!(out)?;
+        }
+        Format::Ndjson => {
+            for (report, df) in &frames {
+                serde_json::to_writer(&mut *out, &record(report, df)?).map_err(to_polars)?;
+                writeln!(out)?;
+            }
+        }
+        Format::Csv => {
+            // A single frame is written as plain CSV, several are told apart by a comment line
+            for (n, (report, df)) in frames.iter().enumerate() {
+                if frames.len() > 1 {
+                    if n > 0 {
+                        writeln!(out)?;
+                    }
+                    writeln!(out, "# {}::{}", report.chapter, report.example)?;
+                }
+                CsvWriter::new(&mut *out).finish(&mut df.clone())?;
+            }
+        }
+        Format::Markdown => {
+            for (n, (report, df)) in frames.iter().enumerate() {
+                if n > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "### {}::{}\n", report.chapter, report.example)?;
+                write_markdown(df, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+//--------------------
+
+fn record(report: &ExampleReport, df: &DataFrame) -> PolarsResult<Value> {
+    Ok(json!({
+        "chapter": report.chapter,
+        "example": report.example,
+        "data": rows(df)?,
+    }))
+}
+
+// One JSON object per row, keyed by column name
+pub fn rows(df: &DataFrame) -> PolarsResult<Vec<Value>> {
+    (0..df.height())
+        .map(|i| {
+            df.get_columns()
+                .iter()
+                .map(|s| Ok((s.name().to_string(), to_json(&s.get(i)?))))
+                .collect::<PolarsResult<Map<_, _>>>()
+                .map(Value::Object)
+        })
+        .collect()
+}
+
+fn to_json(value: &AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => Value::Bool(*b),
+        AnyValue::UInt8(_)
+        | AnyValue::UInt16(_)
+        | AnyValue::UInt32(_)
+        | AnyValue::UInt64(_) => json!(value.extract::<u64>()),
+        AnyValue::Int8(_)
+        | AnyValue::Int16(_)
+        | AnyValue::Int32(_)
+        | AnyValue::Int64(_) => json!(value.extract::<i64>()),
+        // NaN and infinity have no JSON representation and become null
+        // f32 goes through its shortest decimal form, so 0.33 stays 0.33 rather than 0.33000001311302185
+        AnyValue::Float32(f) => json!(Some(*f).filter(|f| f.is_finite()).and_then(|f| f.to_string().parse::<f64>().ok())),
+        AnyValue::Float64(f) => json!(Some(*f).filter(|f| f.is_finite())),
+        AnyValue::List(s) => Value::Array(s.iter().map(|v| to_json(&v)).collect()),
+        AnyValue::Struct(_, _, fields) => Value::Object(
+            fields
+                .iter()
+                .zip(value._iter_struct_av())
+                .map(|(field, v)| (field.name().to_string(), to_json(&v)))
+                .collect(),
+        ),
+        AnyValue::StructOwned(payload) => Value::Object(
+            payload
+                .1
+                .iter()
+                .zip(&payload.0)
+                .map(|(field, v)| (field.name().to_string(), to_json(v)))
+                .collect(),
+        ),
+        // Strings, categoricals and enums, then dates and durations as displayed by Polars
+        other => match other.get_str() {
+            Some(s) => Value::String(s.to_string()),
+            None => Value::String(other.to_string()),
+        },
+    }
+}
+
+pub fn write_markdown(df: &DataFrame, out: &mut impl Write) -> PolarsResult<()> {
+    let columns = df.get_columns();
+
+    let names: Vec<String> = columns.iter().map(|s| escape_cell(s.name())).collect();
+    writeln!(out, "| {} |", names.join(" | "))?;
+    writeln!(out, "|{}|", vec!["---"; columns.len()].join("|"))?;
+
+    for i in 0..df.height() {
+        let cells = columns
+            .iter()
+            .map(|s| Ok(markdown_cell(&s.get(i)?)))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        writeln!(out, "| {} |", cells.join(" | "))?;
+    }
+    Ok(())
+}
+
+fn markdown_cell(value: &AnyValue) -> String {
+    match value {
+        AnyValue::Null => String::new(),
+        other => escape_cell(&other.get_str().map(str::to_string).unwrap_or_else(|| other.to_string())),
+    }
+}
+
+fn escape_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', " ")
+}
+
+fn to_polars(e: serde_json::Error) -> PolarsError {
+    PolarsError::ComputeError(e.to_string().into())
+}
@@ -0,0 +1,57 @@
+use std::env;
+use std::io::{stdout, IsTerminal};
+use std::sync::OnceLock;
+
+use syntect::easy::HighlightLines;
+use syntect::html::highlighted_html_for_string;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
+
+// Syntax highlighting of the source printed by #[print_source]
+// The grammars and themes are bundled with syntect, nothing is downloaded.
+
+// Colours only for an interactive terminal, and never when NO_COLOR is set (https://no-color.org)
+pub fn enabled() -> bool {
+    stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+// Rust source with ANSI colours, unchanged when colours are disabled
+pub fn rust(source: &str) -> String {
+    if !enabled() {
+        return source.to_string();
+    }
+
+    let (syntaxes, theme) = assets();
+    let Some(syntax) = syntaxes.find_syntax_by_extension("rs") else {
+        return source.to_string();
+    };
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut out = String::new();
+    for line in LinesWithEndings::from(source) {
+        match highlighter.highlight_line(line, syntaxes) {
+            Ok(ranges) => out += &as_24_bit_terminal_escaped(&ranges, false),
+            Err(_) => return source.to_string(),
+        }
+    }
+    // Reset, so the output of the example is not coloured
+    out + "\x1b[0m"
+}
+
+// Rust source as a <pre> block with inline styles, for self-contained HTML
+pub fn rust_html(source: &str) -> Option<String> {
+    let (syntaxes, theme) = assets();
+    let syntax = syntaxes.find_syntax_by_extension("rs")?;
+    highlighted_html_for_string(source, syntaxes, syntax, theme).ok()
+}
+
+// Loaded once, on the first highlighted source
+fn assets() -> &'static (SyntaxSet, Theme) {
+    static ASSETS: OnceLock<(SyntaxSet, Theme)> = OnceLock::new();
+    ASSETS.get_or_init(|| {
+        let mut themes = ThemeSet::load_defaults();
+        let theme = themes.themes.remove("base16-ocean.dark").unwrap_or_default();
+        (SyntaxSet::load_defaults_newlines(), theme)
+    })
+}
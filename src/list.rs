@@ -0,0 +1,44 @@
+use crate::registry::*;
+use crate::utilities::*;
+
+// Print every chapter and its examples, optionally filtered by keyword.
+// A chapter matching the keyword is listed with all its examples,
+// otherwise only the matching examples are shown.
+pub fn run(keyword: Option<&str>) {
+    let mut found = false;
+
+    for chapter in chapters() {
+        let examples: Vec<&Example> = match keyword {
+            Some(keyword) if !chapter.matches(keyword) => chapter
+                .examples
+                .iter()
+                .filter(|ex| ex.matches(keyword))
+                .copied()
+                .collect(),
+            _ => chapter.examples.clone(),
+        };
+
+        if examples.is_empty() {
+            continue;
+        }
+        found = true;
+
+        println!("{} (-n {}) {}", chapter.name, chapter.id, chapter.title);
+        println!("    docs     : {}", chapter.url);
+        if chapter.features.is_empty() {
+            println!("    features : -");
+        } else {
+            println!("    features : {}", chapter.features.join(", "));
+        }
+        for example in examples {
+            println!("    {}    {}", example.name, example.title);
+        }
+        hr3();
+    }
+
+    if !found {
+        if let Some(keyword) = keyword {
+            println!("No chapter or example matches {:?}", keyword);
+        }
+    }
+}
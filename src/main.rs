@@ -2,7 +2,32 @@
 mod utilities;
 pub use utilities::*;
 
+mod bench;
+mod datasets;
+mod download;
+mod convert;
+mod display;
+mod error;
+mod explain;
+mod format;
+mod highlight;
+mod list;
+mod paths;
+mod registry;
+mod report;
+mod runner;
+#[cfg(test)]
+mod snapshot;
+mod sysinfo;
+mod tui;
+
 use clap::{Arg, ArgAction, Command};
+use std::io::{stdout, IsTerminal};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use format::Format;
 
 //--------------------
 
@@ -30,12 +55,25 @@ mod ex_019_struct;
 
 fn help_message() {
     println!("Example: cargo run -- -n 1");
+    println!("Run several chapters: cargo run -- -n 1-19 or cargo run -- --all");
+    println!("Step through the examples: cargo run -- -n 14 --step");
+    println!("Run some examples of a chapter: cargo run -- -n 14 -e 4 or cargo run -- -n 14 -e 3..6");
+    println!("Write the returned frames as JSON: cargo run -- -n 14 -e 3 --format json");
+    println!("Time the examples: cargo run -- -n 14 --bench 20 --bench-out bench.csv");
+    println!("List the examples: cargo run -- list [KEYWORD]");
+    println!("Browse, run and read the examples in the terminal: cargo run -- tui");
+    println!("Write the examples and their results as a document: cargo run -- report 1-19 -o examples.html");
+    println!("Show CPUs, thread pool and Polars build: cargo run -- sysinfo, or with 4 threads: cargo run -- --threads 4 sysinfo");
+    println!("Run without downloading datasets: cargo run -- -n 16 --offline");
+    println!("Keep datasets and written files elsewhere: cargo run -- -n 1 --data-dir data --out-dir out");
+    println!("Show whole wide frames: cargo run -- -n 14 --full-width --max-rows 20, or one record per block: --vertical");
 }
 
 //--------------------
 
 
-fn run() {
+fn run() -> ExitCode {
+    let default_dirs = paths::Dirs::default();
     let matches = Command::new("input")
         .version("0.1.0")
         .about("Rust Polars Examples")
@@ -45,55 +83,421 @@ fn run() {
                 .value_name("NUMBER")
                 .long("number")
                 //.default_value("1")
-                .help("Example: cargo run -- -n 1"),
+                .help("Example: cargo run -- -n 1, or a range of chapters: -n 1-19"),
         )
         .arg(
-            Arg::new("flag")
+            Arg::new("all")
+                .long("all")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("numeric_option")
+                .help("Run every chapter and print a summary of the results"),
+        )
+        .arg(
+            Arg::new("examples")
+                .short('e')
+                .value_name("SELECTION")
+                .long("example")
+                .requires("numeric_option")
+                .help("Only run these examples of the chapter, eg: cargo run -- -n 14 -e 4, -e 3..6 or -e 2,5"),
+        )
+        .arg(
+            Arg::new("step")
                 .short('s')
-                .value_name("STRING")
-                .long("string")
-                .help("Example: cargo run -- -s p")
+                .long("step")
+                .action(ArgAction::SetTrue)
+                .help("Step through the examples: next, repeat, view source, skip chapter or quit after each one"),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(Format::NAMES)
+                .default_value("table")
+                .conflicts_with("step")
+                .help("Write the frames returned by the examples as table (printed by the examples), json, ndjson, csv or markdown"),
+        )
+        .arg(
+            Arg::new("bench")
+                .long("bench")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .conflicts_with_all(["step", "format"])
+                .help("Time each example over N runs after a warmup, without printing its output"),
+        )
+        .arg(
+            Arg::new("bench_out")
+                .long("bench-out")
+                .value_name("FILE")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("bench")
+                .help("Also write the benchmark results to FILE, as JSON if it ends in .json and CSV otherwise"),
+        )
+        .arg(
+            Arg::new("threads")
+                .long("threads")
+                .value_name("N")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .global(true)
+                .help("Size of the Polars thread pool, set before any example runs (POLARS_MAX_THREADS)"),
+        )
+        .arg(
+            Arg::new("offline")
+                .long("offline")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Never download: a dataset missing from disk is an error naming the file"),
+        )
+        .arg(
+            Arg::new("download_timeout")
+                .long("download-timeout")
+                .value_name("SECS")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("60")
+                .global(true)
+                .help("Timeout of each download attempt, in seconds"),
+        )
+        .arg(
+            Arg::new("data_dir")
+                .long("data-dir")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .env(paths::DATA_DIR_VAR)
+                .global(true)
+                .help(format!("Where downloaded datasets are stored [default: {}]", default_dirs.data.display())),
+        )
+        .arg(
+            Arg::new("out_dir")
+                .long("out-dir")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(PathBuf))
+                .env(paths::OUT_DIR_VAR)
+                .global(true)
+                .help(format!("Where the examples write their files [default: {}]", default_dirs.out.display())),
+        )
+        .arg(
+            Arg::new("max_rows")
+                .long("max-rows")
+                .value_name("N")
+                .value_parser(clap::value_parser!(i64).range(-1..))
+                .allow_negative_numbers(true)
+                .global(true)
+                .help("Rows shown of each DataFrame, -1 for all [default: 10]"),
+        )
+        .arg(
+            Arg::new("max_cols")
+                .long("max-cols")
+                .value_name("N")
+                .value_parser(clap::value_parser!(i64).range(-1..))
+                .allow_negative_numbers(true)
+                .global(true)
+                .help("Columns shown of each DataFrame, -1 for all [default: 8]"),
+        )
+        .arg(
+            Arg::new("float_precision")
+                .long("float-precision")
+                .value_name("DIGITS")
+                .value_parser(clap::value_parser!(u64).range(0..=16))
+                .global(true)
+                .help("Decimals shown of floats"),
+        )
+        .arg(
+            Arg::new("str_len")
+                .long("str-len")
+                .value_name("N")
+                .value_parser(clap::value_parser!(i64).range(-1..))
+                .allow_negative_numbers(true)
+                .global(true)
+                .help("Characters shown of each string, -1 for all [default: 30]"),
+        )
+        .arg(
+            Arg::new("full_width")
+                .long("full-width")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Show every column and whole strings, however wide the table gets"),
+        )
+        .arg(
+            Arg::new("table_style")
+                .long("table-style")
+                .value_name("STYLE")
+                .value_parser(display::STYLES.to_vec())
+                .global(true)
+                .help("Borders of the printed tables [default: utf8-full-condensed]"),
+        )
+        .arg(
+            Arg::new("vertical")
+                .long("vertical")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Print the DataFrames of print_data one record per block, for very wide rows"),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("List chapters and examples")
+                .arg(
+                    Arg::new("keyword")
+                        .value_name("KEYWORD")
+                        .help("Only show chapters or examples matching KEYWORD, eg: cargo run -- list struct"),
+                ),
+        )
+        .subcommand(Command::new("tui").about("Browse chapters and examples, their source and results, in the terminal"))
+        .subcommand(Command::new("sysinfo").about("Show CPUs, Polars thread pool, version, features, string cache and POLARS_* variables"))
+        .subcommand(
+            Command::new("report")
+                .about("Run chapters and write their examples, source, notes and results as one Markdown or HTML document")
+                .arg(
+                    Arg::new("chapters")
+                        .value_name("CHAPTERS")
+                        .help("Chapters to include, eg 14 or 1-19 [default: all]"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("FILE")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .help("Where to write the document [default: report.md or report.html in the output directory]"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .value_parser(report::ReportFormat::NAMES)
+                        .help("markdown or html [default: from the extension of FILE, else markdown]"),
+                ),
         )
         .get_matches();
 
-    let flag = matches
-        .get_one::<String>("flag")
-        .map(|s| s.as_str());
-
-    match matches.get_one::<String>("numeric_option") {
-        Some(number_str) => match number_str.parse::<i32>() {
-            Ok(0) => check_threads_n(),
-            Ok(1) => ex_001_getting_started::run(flag),
-            Ok(2) => ex_002_categorical::run(flag),
-            Ok(3) => ex_003_enum::run(flag),
-            Ok(4) => ex_004_data_structures::run(flag),
-            Ok(5) => ex_005_contexts::run(flag),
-            Ok(6) => ex_006_expressions::run(flag),
-            Ok(7) => ex_007_lazy_eager_api::run(flag),
-            Ok(8) => ex_008_streaming_api::run(flag),
-            Ok(9) => ex_009_expression_operators::run(flag),
-            Ok(10) => ex_010_expression_column_selections::run(flag),
-            Ok(11) => ex_011_functions::run(flag),
-            Ok(12) => ex_012_casting::run(flag),
-            Ok(13) => ex_013_strings::run(flag),
-            Ok(14) => ex_014_aggregation::run(flag),
-            Ok(15) => ex_015_missing_data::run(flag),
-            Ok(16) => ex_016_window::run(flag),
-            Ok(17) => ex_017_folds::run(flag),
-            Ok(18) => ex_018_lists_arrays::run(flag),
-            Ok(19) => ex_019_struct::run(flag),
-
-            //-----
-            Ok(_) | Err(_) => help_message(),
-        },
-        None => help_message(),
+    if let Some(threads) = matches.get_one::<u64>("threads") {
+        sysinfo::set_threads(*threads as usize);
+    }
+    download::configure(download::DownloadOptions {
+        offline: matches.get_flag("offline"),
+        timeout: Duration::from_secs(*matches.get_one::<u64>("download_timeout").unwrap_or(&60)),
+        ..Default::default()
+    });
+    paths::configure(paths::Dirs {
+        data: matches.get_one::<PathBuf>("data_dir").cloned().unwrap_or(default_dirs.data),
+        out: matches.get_one::<PathBuf>("out_dir").cloned().unwrap_or(default_dirs.out),
+    });
+    display::configure(display::DisplayOptions {
+        max_rows: matches.get_one::<i64>("max_rows").copied(),
+        max_cols: matches.get_one::<i64>("max_cols").copied(),
+        float_precision: matches.get_one::<u64>("float_precision").map(|&n| n as usize),
+        str_len: matches.get_one::<i64>("str_len").copied(),
+        full_width: matches.get_flag("full_width"),
+        style: matches.get_one::<String>("table_style").cloned(),
+        vertical: matches.get_flag("vertical"),
+    });
+
+    if let Some(("list", sub_matches)) = matches.subcommand() {
+        hr1();
+        list::run(sub_matches.get_one::<String>("keyword").map(|s| s.as_str()));
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some(("tui", _)) = matches.subcommand() {
+        if !stdout().is_terminal() {
+            println!("tui needs an interactive terminal");
+            return ExitCode::from(2);
+        }
+        return match tui::run() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Terminal error: {}", e);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if let Some(("sysinfo", _)) = matches.subcommand() {
+        hr1();
+        sysinfo::run();
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some(("report", sub_matches)) = matches.subcommand() {
+        let chapters = match sub_matches.get_one::<String>("chapters").map(|s| runner::parse_selection(s)) {
+            Some(Ok(ids)) => match ids.iter().map(|id| registry::find_chapter(*id).ok_or(*id)).collect() {
+                Ok(chapters) => chapters,
+                Err(id) => {
+                    println!("There is no chapter {}", id);
+                    return ExitCode::from(2);
+                }
+            },
+            Some(Err(e)) => {
+                println!("Invalid chapter selection: {}", e);
+                return ExitCode::from(2);
+            }
+            None => registry::chapters(),
+        };
+        let output = sub_matches.get_one::<PathBuf>("output");
+        let format = match sub_matches.get_one::<String>("format") {
+            Some(name) => report::ReportFormat::parse(name).unwrap_or_default(),
+            None => output.map(|path| report::ReportFormat::from_path(path)).unwrap_or_default(),
+        };
+        let path = match output {
+            Some(path) => path.clone(),
+            None => match paths::out_file(&format!("report.{}", format.extension())) {
+                Ok(path) => path,
+                Err(e) => {
+                    eprintln!("Could not create the output directory: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            },
+        };
+
+        hr1();
+        let sections = report::run(chapters);
+        if let Err(e) = report::write_file(&sections, format, &path) {
+            eprintln!("Could not write {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        let reports: Vec<&runner::ExampleReport> = report::reports(&sections).collect();
+        let failed = reports.iter().filter(|report| report.status.is_failure()).count();
+        println!("Report of {} examples ({} failed) written to {}", reports.len(), failed, path.display());
+        return exit_code(reports);
+    }
+
+    let format = matches
+        .get_one::<String>("format")
+        .and_then(|name| Format::parse(name))
+        .unwrap_or_default();
+
+    let examples = match matches.get_one::<String>("examples").map(|s| runner::parse_selection(s)) {
+        Some(Ok(ids)) => Some(ids),
+        Some(Err(e)) => {
+            println!("Invalid example selection: {}", e);
+            help_message();
+            return ExitCode::from(2);
+        }
+        None => None,
+    };
+
+    let chapter_ids = if matches.get_flag("all") {
+        registry::chapters().iter().map(|chapter| chapter.id).collect()
+    } else {
+        match matches.get_one::<String>("numeric_option").map(|s| runner::parse_selection(s)) {
+            Some(Ok(ids)) => ids,
+            Some(Err(_)) | None => {
+                help_message();
+                return ExitCode::from(2);
+            }
+        }
+    };
+
+    if chapter_ids == [0] {
+        hr1();
+        sysinfo::run();
+        return ExitCode::SUCCESS;
+    }
+
+    let chapters = match chapter_ids
+        .iter()
+        .map(|id| registry::find_chapter(*id).ok_or(*id))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(chapters) => chapters,
+        Err(id) => {
+            println!("There is no chapter {}", id);
+            help_message();
+            return ExitCode::from(2);
+        }
+    };
+
+    if examples.is_some() && chapters.len() > 1 {
+        println!("-e selects examples of a single chapter");
+        help_message();
+        return ExitCode::from(2);
+    }
+
+    for chapter in &chapters {
+        let missing: Vec<u32> = examples
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|id| !chapter.examples.iter().any(|ex| ex.id == *id))
+            .collect();
+        if !missing.is_empty() {
+            println!("{} has no example {:?}", chapter.name, missing);
+            help_message();
+            return ExitCode::from(2);
+        }
+    }
+
+    // Several chapters: carry on after a failure and summarise at the end
+    let options = runner::RunOptions {
+        step: matches.get_flag("step"),
+        examples,
+        keep_going: chapters.len() > 1,
+        format,
+    };
+
+    let run_chapters = || {
+        let mut reports = Vec::new();
+        for chapter in &chapters {
+            hr1();
+            if runner::run_chapter(chapter, &options, &mut reports) == runner::Flow::Quit {
+                break;
+            }
+        }
+        reports
+    };
+
+    // Structured formats keep stdout for the returned frames only,
+    // failures are still reported on stderr
+    let reports = if let Some(runs) = matches.get_one::<u64>("bench") {
+        let mut reports = Vec::new();
+        let mut results = Vec::new();
+        for chapter in &chapters {
+            hr1();
+            bench::bench_chapter(chapter, options.examples.as_deref(), *runs as usize, &mut reports, &mut results);
+        }
+        bench::print_results(&results);
+
+        if let Some(path) = matches.get_one::<PathBuf>("bench_out") {
+            match bench::write_results(&results, path) {
+                Ok(()) => println!("Benchmark results written to {}", path.display()),
+                Err(e) => {
+                    eprintln!("Could not write {}: {}", path.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        reports
+    } else if format.is_structured() {
+        let reports = quietly(run_chapters);
+        if let Err(e) = format::write_reports(&reports, format, &mut stdout().lock()) {
+            eprintln!("Could not write the results as {:?}: {}", format, e);
+            return ExitCode::FAILURE;
+        }
+        reports
+    } else {
+        let reports = run_chapters();
+        if chapters.len() > 1 {
+            runner::print_summary(&reports);
+        }
+        reports
+    };
+
+    exit_code(&reports)
+}
+
+// The first failure decides the exit code, later ones often follow from it
+fn exit_code<'a>(reports: impl IntoIterator<Item = &'a runner::ExampleReport>) -> ExitCode {
+    match reports
+        .into_iter()
+        .filter(|report| report.status.is_failure())
+        .find_map(|report| report.error.as_ref())
+    {
+        Some(error) => error.kind().exit_code(),
+        None => ExitCode::SUCCESS,
     }
 }
 
 //--------------------
 
-fn main() {
+fn main() -> ExitCode {
     //clear_screen();
-    hr1();
-    run();
+    run()
 }
@@ -0,0 +1,83 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Where the examples read and write files, so the binary runs from any directory.
+// Downloaded datasets go to the data directory and files written by the examples
+// to the output directory, both under the user's cache directory by default:
+//
+//     --data-dir DIR  or  POLARS_EXAMPLES_DATA_DIR
+//     --out-dir DIR   or  POLARS_EXAMPLES_OUT_DIR
+//
+// Files bundled with the repository are found relative to the crate, not the
+// current directory, when the data directory has no copy of them.
+
+pub const DATA_DIR_VAR: &str = "POLARS_EXAMPLES_DATA_DIR";
+pub const OUT_DIR_VAR: &str = "POLARS_EXAMPLES_OUT_DIR";
+
+const APP_NAME: &str = "polars-rust-examples";
+
+#[derive(Debug, Clone)]
+pub struct Dirs {
+    pub data: PathBuf,
+    pub out: PathBuf,
+}
+
+impl Default for Dirs {
+    fn default() -> Self {
+        let base = cache_dir().unwrap_or_else(env::temp_dir).join(APP_NAME);
+        Dirs {
+            data: base.join("data"),
+            out: base.join("output"),
+        }
+    }
+}
+
+static DIRS: OnceLock<Dirs> = OnceLock::new();
+
+// Set once from the command line, before any example runs
+pub fn configure(dirs: Dirs) {
+    DIRS.set(dirs).ok();
+}
+
+pub fn dirs() -> &'static Dirs {
+    DIRS.get_or_init(Dirs::default)
+}
+
+// A file in the data directory, which is created if needed
+pub fn data_file(name: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(&dirs().data)?;
+    Ok(dirs().data.join(name))
+}
+
+// A file in the output directory, which is created if needed
+pub fn out_file(name: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(&dirs().out)?;
+    Ok(dirs().out.join(name))
+}
+
+// A file of src/ in the crate the binary was built from, if it is still there.
+// The path is fixed at compile time, so a binary copied elsewhere may not find it:
+// only a fallback for the data directory.
+pub fn bundled_file(name: &str) -> Option<PathBuf> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join(name);
+    path.is_file().then_some(path)
+}
+
+// The per-user cache directory of the platform:
+// $XDG_CACHE_HOME or ~/.cache on Linux, ~/Library/Caches on macOS, %LOCALAPPDATA% on Windows
+fn cache_dir() -> Option<PathBuf> {
+    let var = |name| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+    if cfg!(windows) {
+        var("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library").join("Caches"))
+    } else {
+        var("XDG_CACHE_HOME")
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| var("HOME").map(|home| home.join(".cache")))
+    }
+}
@@ -0,0 +1,216 @@
+use polars::prelude::*;
+
+// Registry of chapters and examples
+// Chapter modules submit a `ChapterInfo`, examples register themselves with
+// #[example(chapter = .., id = ..)], so the CLI can enumerate and execute them
+// without knowing their signatures.
+
+//--------------------
+
+// Value returned by an example
+// Examples return a DataFrame, a Series or nothing at all (ex_001_getting_started::ex000)
+#[derive(Debug, Clone)]
+pub enum Output {
+    Frame(DataFrame),
+    Series(Series),
+    Unit,
+}
+
+impl Output {
+    // Borrow the DataFrame produced by a dependency
+    pub fn frame(&self) -> PolarsResult<&DataFrame> {
+        match self {
+            Output::Frame(df) => Ok(df),
+            other => polars_bail!(ComputeError: "expected a DataFrame input, got {}", other.kind()),
+        }
+    }
+
+    // Borrow the Series produced by a dependency
+    pub fn series(&self) -> PolarsResult<&Series> {
+        match self {
+            Output::Series(s) => Ok(s),
+            other => polars_bail!(ComputeError: "expected a Series input, got {}", other.kind()),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Output::Frame(_) => "DataFrame",
+            Output::Series(_) => "Series",
+            Output::Unit => "()",
+        }
+    }
+}
+
+impl From<DataFrame> for Output {
+    fn from(df: DataFrame) -> Self {
+        Output::Frame(df)
+    }
+}
+
+impl From<Series> for Output {
+    fn from(s: Series) -> Self {
+        Output::Series(s)
+    }
+}
+
+impl From<()> for Output {
+    fn from(_: ()) -> Self {
+        Output::Unit
+    }
+}
+
+// Argument of an example, taken from the output of the example it depends on
+pub trait FromOutput<'a>: Sized {
+    fn from_output(output: &'a Output) -> PolarsResult<Self>;
+}
+
+impl<'a> FromOutput<'a> for &'a DataFrame {
+    fn from_output(output: &'a Output) -> PolarsResult<Self> {
+        output.frame()
+    }
+}
+
+impl<'a> FromOutput<'a> for DataFrame {
+    fn from_output(output: &'a Output) -> PolarsResult<Self> {
+        output.frame().cloned()
+    }
+}
+
+impl<'a> FromOutput<'a> for &'a Series {
+    fn from_output(output: &'a Output) -> PolarsResult<Self> {
+        output.series()
+    }
+}
+
+impl<'a> FromOutput<'a> for Series {
+    fn from_output(output: &'a Output) -> PolarsResult<Self> {
+        output.series().cloned()
+    }
+}
+
+//--------------------
+
+// From #[print_source], registered by #[example]
+#[derive(Debug, Clone, Copy)]
+pub struct ExampleMeta {
+    pub title: Option<&'static str>,
+    pub url: Option<&'static str>,
+    pub notes: Option<&'static str>,
+    pub source: &'static str, // the function as written
+}
+
+impl ExampleMeta {
+    // Source with its docs link and notes, as shown by the step mode and the TUI.
+    // `colour` highlights the source when the terminal allows it.
+    pub fn describe(&self, colour: bool) -> String {
+        let mut text = String::new();
+        if let Some(url) = self.url {
+            text += &format!("// {}\n\n", url);
+        }
+        if colour {
+            text += &crate::highlight::rust(self.source);
+        } else {
+            text += self.source;
+        }
+        if let Some(notes) = self.notes {
+            text += &format!("\nNOTES\n{}", notes);
+        }
+        text
+    }
+}
+
+//--------------------
+
+// Runs an example, given the outputs of its dependencies in `depends_on` order
+pub type Runner = fn(&[Output]) -> PolarsResult<Output>;
+
+// Submitted by #[example], see my_proc_macro
+pub struct Example {
+    pub chapter: u32,
+    pub id: u32,                   // eg 4 for "ex004"
+    pub name: &'static str,        // function name, eg "ex004"
+    pub title: &'static str,       // one-line description
+    pub depends_on: &'static [&'static str], // examples producing the inputs
+    pub runner: Runner,
+    pub expect_error: bool,        // the example demonstrates a failing query
+    pub meta: Option<fn() -> ExampleMeta>, // generated by #[print_source]
+}
+
+inventory::collect!(Example);
+
+impl Example {
+    // Case-insensitive match on the function name or title
+    pub fn matches(&self, keyword: &str) -> bool {
+        let keyword = keyword.to_lowercase();
+        self.name.to_lowercase().contains(&keyword) || self.title.to_lowercase().contains(&keyword)
+    }
+}
+
+// Submitted once per chapter module
+pub struct ChapterInfo {
+    pub id: u32,
+    pub name: &'static str,  // module name, eg "ex_014_aggregation"
+    pub title: &'static str,
+    pub url: &'static str,   // upstream user guide section
+    pub features: &'static [&'static str], // polars cargo features used by the chapter
+}
+
+inventory::collect!(ChapterInfo);
+
+pub struct Chapter {
+    pub id: u32,
+    pub name: &'static str,  // module name, eg "ex_014_aggregation"
+    pub title: &'static str,
+    pub url: &'static str,   // upstream user guide section
+    pub features: &'static [&'static str], // polars cargo features used by the chapter
+    pub examples: Vec<&'static Example>,   // ordered by id
+}
+
+impl Chapter {
+    pub fn example(&self, name: &str) -> Option<&'static Example> {
+        self.examples.iter().find(|ex| ex.name == name).copied()
+    }
+
+    // Case-insensitive match on the chapter name, title or features
+    pub fn matches(&self, keyword: &str) -> bool {
+        let keyword = keyword.to_lowercase();
+        self.name.to_lowercase().contains(&keyword)
+            || self.title.to_lowercase().contains(&keyword)
+            || self.features.iter().any(|f| f.to_lowercase().contains(&keyword))
+    }
+}
+
+//--------------------
+
+// Every registered chapter ordered by id, with its examples
+pub fn chapters() -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = inventory::iter::<ChapterInfo>
+        .into_iter()
+        .map(|info| Chapter {
+            id: info.id,
+            name: info.name,
+            title: info.title,
+            url: info.url,
+            features: info.features,
+            examples: Vec::new(),
+        })
+        .collect();
+    chapters.sort_by_key(|chapter| chapter.id);
+
+    for example in inventory::iter::<Example> {
+        match chapters.iter_mut().find(|chapter| chapter.id == example.chapter) {
+            Some(chapter) => chapter.examples.push(example),
+            None => panic!("{} is registered for chapter {}, which submits no ChapterInfo", example.name, example.chapter),
+        }
+    }
+    for chapter in &mut chapters {
+        chapter.examples.sort_by_key(|example| example.id);
+    }
+
+    chapters
+}
+
+pub fn find_chapter(id: u32) -> Option<Chapter> {
+    chapters().into_iter().find(|chapter| chapter.id == id)
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use polars::prelude::*;
+
+use crate::registry::{Chapter, Example, Output};
+use crate::runner::{self, ExampleReport, Status};
+use crate::utilities::quietly;
+
+// A transcript of the examples as one document, to share instead of terminal screenshots.
+// Every example of the selected chapters runs with its output silenced, then each one is
+// written with its title, docs link, source and notes from #[print_source], and the
+// DataFrame or Series it returned, or its error.
+//
+//     cargo run -- report 1-19 --output polars-examples.html
+//
+// Tables show as many rows as the terminal would, see --max-rows.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Html, // self-contained, styles and highlighting inline
+}
+
+impl ReportFormat {
+    pub const NAMES: [&'static str; 2] = ["markdown", "html"];
+
+    pub fn parse(name: &str) -> Option<ReportFormat> {
+        match name {
+            "markdown" => Some(ReportFormat::Markdown),
+            "html" => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+
+    // Guessed from the file extension: .html or .htm, anything else is Markdown
+    pub fn from_path(path: &Path) -> ReportFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("html" | "htm") => ReportFormat::Html,
+            _ => ReportFormat::Markdown,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+//--------------------
+
+// The examples of a chapter and how their runs went, in id order
+pub struct Section {
+    pub chapter: Chapter,
+    pub entries: Vec<(&'static Example, ExampleReport)>, // reports carry the returned output
+}
+
+// Run every example of the chapters, carrying on after failures
+pub fn run(chapters: Vec<Chapter>) -> Vec<Section> {
+    chapters
+        .into_iter()
+        .map(|chapter| {
+            println!("Running {:03} {}", chapter.id, chapter.title);
+            let mut outputs: HashMap<&'static str, Output> = HashMap::new();
+            let entries = chapter
+                .examples
+                .iter()
+                .map(|example| {
+                    let mut report = quietly(|| runner::execute(&chapter, example, &mut outputs));
+                    report.output = outputs.get(example.name).cloned();
+                    (*example, report)
+                })
+                .collect();
+            Section { chapter, entries }
+        })
+        .collect()
+}
+
+pub fn reports(sections: &[Section]) -> impl Iterator<Item = &ExampleReport> {
+    sections.iter().flat_map(|section| section.entries.iter().map(|(_, report)| report))
+}
+
+pub fn write_file(sections: &[Section], format: ReportFormat, path: &Path) -> PolarsResult<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    match format {
+        ReportFormat::Markdown => write_markdown(sections, &mut out)?,
+        ReportFormat::Html => write_html(sections, &mut out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+// "12 examples, 1 failed" for the document header
+fn totals(sections: &[Section]) -> String {
+    let count = reports(sections).count();
+    let failed = reports(sections).filter(|report| report.status.is_failure()).count();
+    format!("{} examples, {} failed", count, failed)
+}
+
+// What the example returned, cut to the row limit, with a line describing it
+fn result_frame(output: &Output) -> Option<(String, DataFrame)> {
+    let (kind, df) = match output {
+        Output::Frame(df) => ("DataFrame", df.clone()),
+        Output::Series(s) => ("Series", s.clone().into_frame()),
+        Output::Unit => return None,
+    };
+    let limit = crate::display::row_limit();
+    let mut caption = format!("{}, shape: ({}, {})", kind, df.height(), df.width());
+    if df.height() > limit {
+        caption += &format!(", first {} rows", limit);
+    }
+    Some((caption, df.head(Some(limit))))
+}
+
+// The error of a failed example, or the one an expect_error example demonstrates
+fn outcome(report: &ExampleReport) -> Option<String> {
+    let error = report.error.as_ref()?;
+    match report.status {
+        Status::ExpectedError => Some(format!("Expected error: {}", error)),
+        status => Some(format!("{} ({}): {}", status.label(), error.kind().label(), error)),
+    }
+}
+
+//--------------------
+
+fn write_markdown(sections: &[Section], out: &mut impl Write) -> PolarsResult<()> {
+    writeln!(out, "# Rust Polars Examples\n")?;
+    writeln!(out, "Polars {}, {}.\n", polars::VERSION, totals(sections))?;
+    for section in sections {
+        let chapter = &section.chapter;
+        writeln!(out, "- [{:03} {}](#{})", chapter.id, chapter.title, chapter.name)?;
+    }
+
+    for section in sections {
+        let chapter = &section.chapter;
+        writeln!(out, "\n<a id=\"{}\"></a>\n", chapter.name)?;
+        writeln!(out, "## {:03} {}\n", chapter.id, chapter.title)?;
+        writeln!(out, "<{}>", chapter.url)?;
+
+        for (example, report) in &section.entries {
+            let meta = example.meta.map(|meta| meta());
+            writeln!(out, "\n### {} {}\n", example.name, example.title)?;
+            if let Some(url) = meta.and_then(|meta| meta.url) {
+                writeln!(out, "<{}>\n", url)?;
+            }
+            match meta {
+                Some(meta) => writeln!(out, "```rust\n{}\n```", meta.source.trim_end())?,
+                None => writeln!(out, "*Source not available, {} is not annotated with `#[print_source]`*", example.name)?,
+            }
+            if let Some(notes) = meta.and_then(|meta| meta.notes) {
+                writeln!(out, "\n**Notes**\n\n{}", notes.trim_end())?;
+            }
+            if let Some(message) = outcome(report) {
+                writeln!(out, "\n```text\n{}\n```", message.trim_end())?;
+            }
+            if let Some((caption, df)) = report.output.as_ref().and_then(result_frame) {
+                writeln!(out, "\n**Result**: {}\n", caption)?;
+                crate::format::write_markdown(&df, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+//--------------------
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+pre { padding: 0.75rem; overflow-x: auto; font-size: 0.85rem; }
+pre.notes { background: #f6f6f6; white-space: pre-wrap; }
+pre.error { background: #fff0f0; border-left: 3px solid #c33; white-space: pre-wrap; }
+table { border-collapse: collapse; font-size: 0.85rem; margin-bottom: 1rem; }
+th, td { border: 1px solid #ccc; padding: 0.2rem 0.5rem; text-align: left; }
+th span { display: block; color: #777; font-weight: normal; }
+td.null { color: #999; font-style: italic; }
+h3 { margin-top: 2rem; border-top: 1px solid #eee; padding-top: 1rem; }
+";
+
+fn write_html(sections: &[Section], out: &mut impl Write) -> PolarsResult<()> {
+    writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>Rust Polars Examples</title>\n<style>{}</style>\n</head>\n<body>", STYLE)?;
+    writeln!(out, "<h1>Rust Polars Examples</h1>")?;
+    writeln!(out, "<p>Polars {}, {}.</p>\n<ul>", polars::VERSION, totals(sections))?;
+    for section in sections {
+        let chapter = &section.chapter;
+        writeln!(out, "<li><a href=\"#{}\">{:03} {}</a></li>", chapter.name, chapter.id, escape(chapter.title))?;
+    }
+    writeln!(out, "</ul>")?;
+
+    for section in sections {
+        let chapter = &section.chapter;
+        writeln!(out, "<section id=\"{}\">", chapter.name)?;
+        writeln!(out, "<h2>{:03} {}</h2>", chapter.id, escape(chapter.title))?;
+        writeln!(out, "<p>{}</p>", link(chapter.url))?;
+
+        for (example, report) in &section.entries {
+            let meta = example.meta.map(|meta| meta());
+            writeln!(out, "<h3 id=\"{}-{}\">{} {}</h3>", chapter.name, example.name, example.name, escape(example.title))?;
+            if let Some(url) = meta.and_then(|meta| meta.url) {
+                writeln!(out, "<p>{}</p>", link(url))?;
+            }
+            match meta {
+                Some(meta) => match crate::highlight::rust_html(meta.source) {
+                    Some(html) => write!(out, "{}", html)?,
+                    None => writeln!(out, "<pre><code>{}</code></pre>", escape(meta.source))?,
+                },
+                None => writeln!(
+                    out,
+                    "<p><em>Source not available, {} is not annotated with <code>#[print_source]</code></em></p>",
+                    example.name
+                )?,
+            }
+            if let Some(notes) = meta.and_then(|meta| meta.notes) {
+                writeln!(out, "<h4>Notes</h4>\n<pre class=\"notes\">{}</pre>", escape(notes.trim_end()))?;
+            }
+            if let Some(message) = outcome(report) {
+                writeln!(out, "<pre class=\"error\">{}</pre>", escape(message.trim_end()))?;
+            }
+            if let Some((caption, df)) = report.output.as_ref().and_then(result_frame) {
+                writeln!(out, "<h4>Result</h4>\n<p>{}</p>", caption)?;
+                write_html_table(&df, out)?;
+            }
+        }
+        writeln!(out, "</section>")?;
+    }
+    writeln!(out, "</body>\n</html>")?;
+    Ok(())
+}
+
+// Column names with their dtypes, then the rows
+fn write_html_table(df: &DataFrame, out: &mut impl Write) -> PolarsResult<()> {
+    let columns = df.get_columns();
+    writeln!(out, "<table>\n<thead><tr>")?;
+    for s in columns {
+        writeln!(out, "<th>{}<span>{}</span></th>", escape(s.name()), escape(&s.dtype().to_string()))?;
+    }
+    writeln!(out, "</tr></thead>\n<tbody>")?;
+    for i in 0..df.height() {
+        write!(out, "<tr>")?;
+        for s in columns {
+            match s.get(i)? {
+                AnyValue::Null => write!(out, "<td class=\"null\">null</td>")?,
+                value => {
+                    let text = value.get_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+                    write!(out, "<td>{}</td>", escape(&text))?
+                }
+            }
+        }
+        writeln!(out, "</tr>")?;
+    }
+    writeln!(out, "</tbody>\n</table>")?;
+    Ok(())
+}
+
+fn link(url: &str) -> String {
+    format!("<a href=\"{0}\">{0}</a>", escape(url))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{stdin, stdout, Write};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use polars::prelude::*;
+
+use crate::error::Error;
+use crate::format::Format;
+use crate::registry::{Chapter, Example, Output};
+use crate::utilities::*;
+
+#[derive(Debug, Default)]
+pub struct RunOptions {
+    pub step: bool,               // prompt for the next action after each example
+    pub examples: Option<Vec<u32>>, // only run these example ids, eg [3, 4, 5, 6]
+    pub keep_going: bool,         // record failures and carry on with the next example
+    pub format: Format,           // how the returned frames are written, see format::write_reports
+}
+
+// Parse a selection such as "4", "3..6", "1-19" or "2,5..7".
+// Ranges are inclusive: "3..6" selects 3, 4, 5 and 6.
+pub fn parse_selection(input: &str) -> Result<Vec<u32>, String> {
+    let mut ids = Vec::new();
+
+    for part in input.split(',').map(str::trim) {
+        let range = part.split_once("..").or_else(|| part.split_once('-'));
+        let parsed = match range {
+            Some((start, end)) => {
+                let start: u32 = start.parse().map_err(|_| format!("invalid range start in {:?}", part))?;
+                let end: u32 = end.trim_start_matches('=').parse().map_err(|_| format!("invalid range end in {:?}", part))?;
+                if start > end {
+                    return Err(format!("empty range {:?}", part));
+                }
+                (start..=end).collect()
+            }
+            None => vec![part.parse().map_err(|_| format!("invalid number {:?}", part))?],
+        };
+        ids.extend(parsed);
+    }
+
+    ids.sort_unstable();
+    ids.dedup();
+    Ok(ids)
+}
+
+//--------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    ExpectedError, // the example demonstrates a failing query
+    Failed,
+    Skipped,       // an example it depends on failed
+}
+
+impl Status {
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Failed | Status::Skipped)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::ExpectedError => "ok (expected error)",
+            Status::Failed => "FAILED",
+            Status::Skipped => "SKIPPED",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExampleReport {
+    pub chapter: &'static str,
+    pub example: &'static str,
+    pub status: Status,
+    pub elapsed: Duration,
+    pub error: Option<Error>,
+    pub output: Option<Output>, // what a selected example returned
+}
+
+//--------------------
+
+// What to do next in step mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Quit, // stop running any further chapter
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepAction {
+    Next,
+    Repeat,
+    SkipChapter,
+    ShowSource,
+    Quit,
+}
+
+// Execute the examples of a chapter in order, feeding each example the
+// outputs of the examples listed in its `depends_on`.
+// When only some examples are selected, the examples they depend on are run
+// first without printing their output.
+// Without `keep_going` the chapter stops at the first failure.
+pub fn run_chapter(chapter: &Chapter, options: &RunOptions, reports: &mut Vec<ExampleReport>) -> Flow {
+    println!("{:03} {} examples", chapter.id, chapter.title);
+    println!("{}", chapter.url);
+
+    let selected = selected_examples(chapter, options.examples.as_deref());
+    let needed = with_dependencies(chapter, &selected);
+
+    if options.step {
+        println!("Running examples individually.\n");
+    } else if options.examples.is_some() {
+        println!("Running selected examples.\n");
+    } else {
+        println!("Running all examples.\n");
+    }
+
+    let mut outputs: HashMap<&'static str, Output> = HashMap::new();
+
+    for example in chapter.examples.iter().filter(|ex| needed.contains(ex.name)) {
+        // Producer of an input for a selected example
+        if !selected.contains(example.name) {
+            let report = quietly(|| execute(chapter, example, &mut outputs));
+            let failed = report.status.is_failure();
+            if failed {
+                print_failure(&report);
+            }
+            reports.push(report);
+            if failed && !options.keep_going {
+                break;
+            }
+            continue;
+        }
+
+        let mut report = run_visibly(chapter, example, &mut outputs, options);
+
+        if options.step {
+            loop {
+                match prompt_step() {
+                    StepAction::Next => break,
+                    StepAction::Repeat => report = run_visibly(chapter, example, &mut outputs, options),
+                    StepAction::ShowSource => {
+                        hr3();
+                        match example.meta {
+                            Some(meta) => println!("\n{}", meta().describe(true)),
+                            None => println!("Source not available: {} is not annotated with #[print_source]", example.name),
+                        }
+                        hr3();
+                    }
+                    StepAction::SkipChapter => {
+                        reports.push(report);
+                        return Flow::Continue;
+                    }
+                    StepAction::Quit => {
+                        reports.push(report);
+                        return Flow::Quit;
+                    }
+                }
+            }
+        }
+
+        let failed = report.status.is_failure();
+        reports.push(report);
+        if failed && !options.keep_going {
+            break;
+        }
+    }
+
+    Flow::Continue
+}
+
+fn run_visibly(
+    chapter: &Chapter,
+    example: &Example,
+    outputs: &mut HashMap<&'static str, Output>,
+    options: &RunOptions,
+) -> ExampleReport {
+    if options.step {
+        clear_screen();
+    }
+    hr2();
+
+    let mut report = execute(chapter, example, outputs);
+    // Kept only to be written out after the run, a table was already printed by the example
+    if report.status == Status::Ok && options.format.is_structured() {
+        report.output = outputs.get(example.name).cloned();
+    }
+    if report.status.is_failure() {
+        print_failure(&report);
+    }
+    report
+}
+
+// Readable report of a failed example: which one, which PolarsError variant and why
+// Written to stderr so that it also shows when stdout carries structured output.
+pub fn print_failure(report: &ExampleReport) {
+    let Some(error) = &report.error else {
+        return;
+    };
+    stdout().flush().ok();
+    eprintln!();
+    eprintln!("{}::{} {} ({})", report.chapter, report.example, report.status.label(), error.kind().label());
+    eprintln!("    error   : {}", error.variant());
+    eprintln!("    message : {}", error);
+}
+
+// Ask what to do after an example. End of input (eg piped stdin) moves on.
+fn prompt_step() -> StepAction {
+    let mut stdout = stdout();
+    loop {
+        print!("\n[Enter] next  [r] repeat  [v] view source  [s] skip chapter  [q] quit > ");
+        stdout.flush().ok();
+
+        let mut line = String::new();
+        match stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => return StepAction::Next,
+            Ok(_) => {}
+        }
+
+        match line.trim().to_lowercase().as_str() {
+            "" | "n" => return StepAction::Next,
+            "r" => return StepAction::Repeat,
+            "v" => return StepAction::ShowSource,
+            "s" => return StepAction::SkipChapter,
+            "q" => return StepAction::Quit,
+            other => println!("Unknown choice {:?}", other),
+        }
+    }
+}
+
+// Run one example and record the outcome, storing its output for later examples
+pub fn execute(chapter: &Chapter, example: &Example, outputs: &mut HashMap<&'static str, Output>) -> ExampleReport {
+    if let Some(dependency) = missing_dependency(example, outputs) {
+        return ExampleReport {
+            chapter: chapter.name,
+            example: example.name,
+            status: Status::Skipped,
+            elapsed: Duration::ZERO,
+            error: Some(Error::MissingInput(dependency)),
+            output: None,
+        };
+    }
+
+    let start = Instant::now();
+    let result = run_example(example, outputs);
+    let elapsed = start.elapsed();
+
+    let (status, error) = match result {
+        Ok(output) => {
+            outputs.insert(example.name, output);
+            (Status::Ok, None)
+        }
+        // The example already printed its error as part of the lesson
+        Err(e) if example.expect_error => (Status::ExpectedError, Some(e)),
+        Err(e) => (Status::Failed, Some(e)),
+    };
+
+    ExampleReport {
+        chapter: chapter.name,
+        example: example.name,
+        status,
+        elapsed,
+        error,
+        output: None,
+    }
+}
+
+// Names of the examples picked by their ids, or of every example
+pub fn selected_examples(chapter: &Chapter, ids: Option<&[u32]>) -> HashSet<&'static str> {
+    match ids {
+        Some(ids) => chapter
+            .examples
+            .iter()
+            .filter(|ex| ids.contains(&ex.id))
+            .map(|ex| ex.name)
+            .collect(),
+        None => chapter.examples.iter().map(|ex| ex.name).collect(),
+    }
+}
+
+// The selected examples plus everything they transitively depend on
+pub fn with_dependencies(chapter: &Chapter, selected: &HashSet<&'static str>) -> HashSet<&'static str> {
+    let mut needed = HashSet::new();
+    let mut pending: Vec<&'static str> = selected.iter().copied().collect();
+
+    while let Some(name) = pending.pop() {
+        if needed.insert(name) {
+            if let Some(example) = chapter.example(name) {
+                pending.extend(example.depends_on);
+            }
+        }
+    }
+
+    needed
+}
+
+fn missing_dependency(example: &Example, outputs: &HashMap<&'static str, Output>) -> Option<&'static str> {
+    example
+        .depends_on
+        .iter()
+        .find(|name| !outputs.contains_key(*name))
+        .copied()
+}
+
+// Run the example, turning a panic into an error as well
+pub fn run_example(example: &Example, outputs: &HashMap<&'static str, Output>) -> Result<Output, Error> {
+    let inputs: Vec<Output> = example
+        .depends_on
+        .iter()
+        .filter_map(|name| outputs.get(name).cloned())
+        .collect();
+
+    match catch_unwind(AssertUnwindSafe(|| (example.runner)(&inputs))) {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(e)) => Err(Error::Polars(e)),
+        Err(panic) => Err(Error::Panic(panic_message(panic.as_ref()))),
+    }
+}
+
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+//--------------------
+
+// Print one line per example with its status, elapsed time and error message
+pub fn print_summary(reports: &[ExampleReport]) {
+    let chapter_width = reports.iter().map(|r| r.chapter.len()).max().unwrap_or(0).max("CHAPTER".len());
+    let status_width = reports.iter().map(|r| r.status.label().len()).max().unwrap_or(0).max("STATUS".len());
+
+    hr1();
+    println!("SUMMARY");
+    println!(
+        "{:<chapter_width$}  {:<7}  {:<status_width$}  {:>10}  ERROR",
+        "CHAPTER", "EXAMPLE", "STATUS", "ELAPSED",
+    );
+    hr3();
+    for report in reports {
+        // Keep the table on one line per example
+        let error = report
+            .error
+            .as_ref()
+            .map(|e| e.to_string().lines().next().unwrap_or("").to_string())
+            .unwrap_or_default();
+        println!(
+            "{:<chapter_width$}  {:<7}  {:<status_width$}  {:>8.1}ms  {}",
+            report.chapter,
+            report.example,
+            report.status.label(),
+            report.elapsed.as_secs_f64() * 1000.0,
+            error,
+        );
+    }
+    hr3();
+
+    let failed = reports.iter().filter(|r| r.status.is_failure()).count();
+    println!("{} examples, {} passed, {} failed", reports.len(), reports.len() - failed, failed);
+}
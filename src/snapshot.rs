@@ -0,0 +1,111 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use polars::prelude::*;
+
+use crate::registry::{find_chapter, Output};
+use crate::runner::{self, Status};
+
+// Snapshot tests, generated by #[example(..., snapshot)] as `cargo test` tests.
+// The example runs after the examples it depends on, which produce its inputs,
+// and its DataFrame or Series is compared to snapshots/<chapter>/<example>.snap:
+// the shape, the schema, then one JSON object per row.
+// When Polars output changes on purpose, rewrite the snapshots with
+//
+//     BLESS_SNAPSHOTS=1 cargo test
+//
+// and review the diff of the .snap files.
+
+const BLESS_VAR: &str = "BLESS_SNAPSHOTS";
+
+pub fn check(chapter_id: u32, name: &str) {
+    let chapter = find_chapter(chapter_id).unwrap_or_else(|| panic!("no chapter {}", chapter_id));
+    let example = chapter
+        .example(name)
+        .unwrap_or_else(|| panic!("no example {} in {}", name, chapter.name));
+
+    // Fixture inputs: the outputs of the examples it depends on
+    let needed = runner::with_dependencies(&chapter, &HashSet::from([example.name]));
+    let mut outputs = HashMap::new();
+    for ex in chapter.examples.iter().filter(|ex| needed.contains(ex.name)) {
+        let report = runner::execute(&chapter, ex, &mut outputs);
+        if report.status != Status::Ok {
+            let error = report.error.map(|e| e.to_string()).unwrap_or_default();
+            panic!("{}::{} {}: {}", chapter.name, ex.name, report.status.label(), error);
+        }
+    }
+
+    let actual = match render(&outputs[example.name]) {
+        Ok(text) => format!("# {}::{}\n{}", chapter.name, name, text),
+        Err(e) => panic!("{}::{} cannot be snapshotted: {}", chapter.name, name, e),
+    };
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(chapter.name)
+        .join(format!("{}.snap", name));
+
+    if env::var_os(BLESS_VAR).is_some_and(|value| !value.is_empty() && value != "0") {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(_) => panic!("no snapshot at {}, run with {}=1 to create it", path.display(), BLESS_VAR),
+    };
+    if expected != actual {
+        panic!(
+            "{}::{} differs from {}, run with {}=1 if the change is expected\n{}",
+            chapter.name,
+            name,
+            path.display(),
+            BLESS_VAR,
+            diff(&expected, &actual),
+        );
+    }
+}
+
+// Shape, schema and rows of the output
+fn render(output: &Output) -> PolarsResult<String> {
+    let (kind, df) = match output {
+        Output::Frame(df) => ("DataFrame", df.clone()),
+        Output::Series(s) => ("Series", s.clone().into_frame()),
+        Output::Unit => polars_bail!(ComputeError: "the example returns no DataFrame or Series"),
+    };
+
+    let mut text = format!("{} shape: ({}, {})\n\nschema\n", kind, df.height(), df.width());
+    for s in df.get_columns() {
+        text += &format!("{}: {}\n", s.name(), s.dtype());
+    }
+    text += "\nrows\n";
+    for row in crate::format::rows(&df)? {
+        text += &format!("{}\n", row);
+    }
+    Ok(text)
+}
+
+// The differing lines, expected (-) then actual (+)
+fn diff(expected: &str, actual: &str) -> String {
+    const MAX_LINES: usize = 20;
+
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let differing: Vec<usize> = (0..expected.len().max(actual.len()))
+        .filter(|&i| expected.get(i) != actual.get(i))
+        .collect();
+
+    let mut out = String::new();
+    for &i in differing.iter().take(MAX_LINES) {
+        out += &format!("line {}\n", i + 1);
+        out += &format!("- {}\n", expected.get(i).unwrap_or(&"<none>"));
+        out += &format!("+ {}\n", actual.get(i).unwrap_or(&"<none>"));
+    }
+    if differing.len() > MAX_LINES {
+        out += &format!("... and {} more lines\n", differing.len() - MAX_LINES);
+    }
+    out
+}
@@ -0,0 +1,48 @@
+use std::env;
+use std::thread;
+
+use crate::utilities::*;
+
+// The features cargo resolved for polars, its default ones included, recorded by build.rs
+const POLARS_FEATURES: &str = env!("POLARS_FEATURES");
+
+// Print what the examples run on: CPUs, the Polars thread pool and build,
+// the global string cache and the POLARS_* environment variables
+pub fn run() {
+    println!("SYSTEM INFO");
+    hr3();
+
+    match thread::available_parallelism() {
+        Ok(num) => println!("Logical CPUs        : {}", num),
+        Err(e) => println!("Logical CPUs        : unknown ({})", e),
+    }
+    // Creates the pool if no example has used it yet, so --threads applies
+    println!("Polars thread pool  : {} threads", polars_core::POOL.current_num_threads());
+    println!("Polars version      : {}", polars::VERSION);
+    match polars_features() {
+        features if features.is_empty() => println!("Polars features     : unknown"),
+        features => println!("Polars features     : {}", features.join(", ")),
+    }
+    println!("String cache enabled: {}", polars::using_string_cache());
+
+    hr3();
+    let mut vars: Vec<(String, String)> = env::vars().filter(|(name, _)| name.starts_with("POLARS_")).collect();
+    vars.sort();
+    if vars.is_empty() {
+        println!("No POLARS_* environment variables set");
+    }
+    for (name, value) in vars {
+        println!("{} = {}", name, value);
+    }
+}
+
+// Size the Polars thread pool, which reads POLARS_MAX_THREADS once when it is first used.
+// Must run before any example touches Polars.
+pub fn set_threads(n: usize) {
+    env::set_var("POLARS_MAX_THREADS", n.to_string());
+}
+
+// Eg ["concat_str", "csv", "dtype-categorical", ...], empty if build.rs could not run cargo metadata
+fn polars_features() -> Vec<&'static str> {
+    POLARS_FEATURES.split(',').filter(|feature| !feature.is_empty()).collect()
+}
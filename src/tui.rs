@@ -0,0 +1,292 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::io;
+use std::panic;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::registry::{chapters, Chapter, Output};
+use crate::runner;
+
+// Terminal browser: chapters and examples on the left, the source of the
+// selected example and the result of its last run on the right.
+// Examples are executed by the runner, with their own printing silenced.
+
+// A line of the left pane
+#[derive(Debug, Clone, Copy)]
+enum Entry {
+    Chapter(usize),
+    Example(usize, usize), // chapter index, example index
+}
+
+// Pane scrolled by PgUp/PgDn and the arrow keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Source,
+    Result,
+}
+
+struct App {
+    chapters: Vec<Chapter>,
+    entries: Vec<Entry>,
+    list: ListState,
+    outputs: Vec<HashMap<&'static str, Output>>, // per chapter, the outputs of the examples run so far
+    results: HashMap<(usize, usize), String>,     // rendered result of the last run of each example
+    focus: Pane,
+    source_scroll: (u16, u16),
+    result_scroll: (u16, u16),
+    status: String,
+}
+
+pub fn run() -> io::Result<()> {
+    // Show whole frames, the result pane scrolls. Settings from the environment win.
+    for (name, value) in [("POLARS_FMT_MAX_ROWS", "-1"), ("POLARS_FMT_MAX_COLS", "-1"), ("POLARS_TABLE_WIDTH", "1000")] {
+        if env::var_os(name).is_none() {
+            env::set_var(name, value);
+        }
+    }
+
+    let mut terminal = ratatui::try_init()?;
+    let result = App::new().event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl App {
+    fn new() -> Self {
+        let chapters = chapters();
+        let entries = chapters
+            .iter()
+            .enumerate()
+            .flat_map(|(c, chapter)| {
+                std::iter::once(Entry::Chapter(c)).chain((0..chapter.examples.len()).map(move |e| Entry::Example(c, e)))
+            })
+            .collect::<Vec<_>>();
+        // Nothing to select in a build without chapters
+        let selected = (!entries.is_empty()).then_some(0);
+
+        App {
+            outputs: chapters.iter().map(|_| HashMap::new()).collect(),
+            chapters,
+            entries,
+            list: ListState::default().with_selected(selected),
+            results: HashMap::new(),
+            focus: Pane::Source,
+            source_scroll: (0, 0),
+            result_scroll: (0, 0),
+            status: String::new(),
+        }
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Tab => {
+                    self.focus = match self.focus {
+                        Pane::Source => Pane::Result,
+                        Pane::Result => Pane::Source,
+                    }
+                }
+                KeyCode::PageDown => self.scroll(10, 0),
+                KeyCode::PageUp => self.scroll(-10, 0),
+                KeyCode::Right | KeyCode::Char('l') => self.scroll(0, 8),
+                KeyCode::Left | KeyCode::Char('h') => self.scroll(0, -8),
+                KeyCode::Home => *self.scroll_mut() = (0, 0),
+                KeyCode::Enter | KeyCode::Char('r') => {
+                    if let Some(Entry::Example(c, e)) = self.selected() {
+                        // Show that something is happening, examples may download data
+                        self.status = format!("Running {}::{}...", self.chapters[c].name, self.chapters[c].examples[e].name);
+                        terminal.draw(|frame| self.draw(frame))?;
+                        // r reruns the examples producing the inputs as well
+                        if key.code == KeyCode::Char('r') {
+                            self.outputs[c].clear();
+                        }
+                        self.run_example(c, e);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn selected(&self) -> Option<Entry> {
+        self.list.selected().and_then(|i| self.entries.get(i).copied())
+    }
+
+    fn select(&mut self, step: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0) as isize;
+        let next = (current + step).clamp(0, self.entries.len() as isize - 1);
+        self.list.select(Some(next as usize));
+        self.source_scroll = (0, 0);
+        self.result_scroll = (0, 0);
+    }
+
+    fn scroll_mut(&mut self) -> &mut (u16, u16) {
+        match self.focus {
+            Pane::Source => &mut self.source_scroll,
+            Pane::Result => &mut self.result_scroll,
+        }
+    }
+
+    fn scroll(&mut self, down: i32, right: i32) {
+        let (y, x) = self.scroll_mut();
+        *y = (*y as i32 + down).max(0) as u16;
+        *x = (*x as i32 + right).max(0) as u16;
+    }
+
+    // Run an example, first running the examples it depends on unless their output is already there
+    fn run_example(&mut self, c: usize, e: usize) {
+        let chapter = &self.chapters[c];
+        let example = &chapter.examples[e];
+        let outputs = &mut self.outputs[c];
+        let needed = runner::with_dependencies(chapter, &HashSet::from([example.name]));
+
+        let report = silently(|| {
+            for ex in chapter.examples.iter().filter(|ex| needed.contains(ex.name)) {
+                if ex.name != example.name && outputs.contains_key(ex.name) {
+                    continue;
+                }
+                let report = runner::execute(chapter, ex, outputs);
+                if ex.name == example.name || report.status.is_failure() {
+                    return report;
+                }
+            }
+            unreachable!("{} is in its own dependencies", example.name)
+        });
+
+        let mut text = format!(
+            "{}::{} {} in {:.1}ms\n",
+            report.chapter,
+            report.example,
+            report.status.label(),
+            report.elapsed.as_secs_f64() * 1000.0,
+        );
+        match (&report.error, outputs.get(example.name)) {
+            (Some(error), _) => {
+                text += &format!("\n{} ({})\n\n{}\n", error.variant(), error.kind().label(), error);
+            }
+            (None, Some(Output::Frame(df))) => text += &format!("\n{}\n", df),
+            (None, Some(Output::Series(s))) => text += &format!("\n{}\n", s),
+            (None, _) => text += "\nThe example returns no DataFrame or Series\n",
+        }
+
+        self.status = text.lines().next().unwrap_or_default().to_string();
+        self.results.insert((c, e), text);
+        self.result_scroll = (0, 0);
+    }
+
+    //--------------------
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] = Layout::horizontal([Constraint::Length(48), Constraint::Min(0)]).areas(main);
+        let [top, bottom] = Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(right);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| match *entry {
+                Entry::Chapter(c) => {
+                    let chapter = &self.chapters[c];
+                    ListItem::new(Line::from(format!("{:03} {}", chapter.id, chapter.title)).bold())
+                }
+                Entry::Example(c, e) => {
+                    let example = &self.chapters[c].examples[e];
+                    ListItem::new(format!("    {}  {}", example.name, example.title))
+                }
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Examples "))
+            .highlight_style(Style::new().reversed())
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, left, &mut self.list);
+
+        let (source_title, source, result) = match self.selected() {
+            Some(Entry::Example(c, e)) => {
+                let example = &self.chapters[c].examples[e];
+                let source = match example.meta {
+                    Some(meta) => meta().describe(false),
+                    None => format!("Source not available: {} is not annotated with #[print_source]", example.name),
+                };
+                let result = self
+                    .results
+                    .get(&(c, e))
+                    .cloned()
+                    .unwrap_or_else(|| "Press Enter to run the example".to_string());
+                (format!(" {}::{} ", self.chapters[c].name, example.name), source, result)
+            }
+            Some(Entry::Chapter(c)) => {
+                let chapter = &self.chapters[c];
+                let features = if chapter.features.is_empty() { "-".to_string() } else { chapter.features.join(", ") };
+                let info = format!(
+                    "{}\n\ndocs     : {}\nfeatures : {}\nexamples : {}",
+                    chapter.title,
+                    chapter.url,
+                    features,
+                    chapter.examples.len(),
+                );
+                (format!(" {} ", chapter.name), info, "Select an example to run it".to_string())
+            }
+            None => (String::new(), String::new(), String::new()),
+        };
+
+        frame.render_widget(
+            Paragraph::new(source)
+                .block(self.pane_block(Pane::Source, source_title))
+                .scroll(self.source_scroll),
+            top,
+        );
+        frame.render_widget(
+            Paragraph::new(result)
+                .block(self.pane_block(Pane::Result, " Result ".to_string()))
+                .scroll(self.result_scroll),
+            bottom,
+        );
+
+        let keys = "↑/↓ select  Enter run  r rerun with inputs  Tab switch pane  PgUp/PgDn/←/→ scroll  q quit";
+        let help_line = if self.status.is_empty() { keys.to_string() } else { format!("{}  |  {}", self.status, keys) };
+        frame.render_widget(Paragraph::new(help_line).dim(), help);
+    }
+
+    fn pane_block(&self, pane: Pane, title: String) -> Block<'static> {
+        let block = Block::bordered().title(title);
+        if self.focus == pane {
+            block.border_style(Style::new().fg(Color::Yellow))
+        } else {
+            block
+        }
+    }
+}
+
+// Run f without letting the example write over the screen:
+// stdout, stderr (eg Polars warnings) and panic messages are silenced
+fn silently<T>(f: impl FnOnce() -> T) -> T {
+    let _stdout = gag::Gag::stdout().ok();
+    let _stderr = gag::Gag::stderr().ok();
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let out = f();
+    panic::set_hook(hook);
+    out
+}
@@ -1,6 +1,6 @@
-use std::io::{stdin, stdout, Read, Write};
-use polars::frame::DataFrame;
-use std::thread;
+use std::io::{stdout, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use polars::prelude::{DataFrame, IntoSeries, Series};
 
 // Utilities
 #[macro_export]
@@ -35,54 +35,89 @@ pub fn print_type<T>(_: &T) {
 }
 
 pub fn clear_screen() {
+    // Only clear an interactive terminal, not a file or pipe
+    if !stdout().is_terminal() {
+        return;
+    }
     // \x1B[2J: Clears the entire screen. \x1B is the escape character (ASCII 27), and [2J is the command to clear the screen
     // \x1B[1;1H: Moves the cursor to the top-left corner of the screen (row 1, column 1).
     println!("\x1B[2J\x1B[1;1H");
 }
 
-pub fn pause() {
-    let mut stdout = stdout();
-    stdout.write(b"\n[ Press Enter to continue ] ").unwrap();
-    stdout.flush().unwrap();
-    stdin().read(&mut [0]).unwrap();
+// Off while bench.rs times the examples, so that the timings leave out what
+// print_data, #[print_source] and #[explain] print: formatting the frames,
+// highlighting the source and rendering the query plans
+static PRINTING: AtomicBool = AtomicBool::new(true);
+
+pub fn set_printing(on: bool) {
+    PRINTING.store(on, Ordering::Relaxed);
+}
+
+pub fn printing() -> bool {
+    PRINTING.load(Ordering::Relaxed)
 }
 
+// Rendered as set by the display options, see display.rs
 pub fn print_data(input: &DataFrame) {
+    if !printing() {
+        return;
+    }
     print_type(&input);
-    println!("DATA      : {:?}", &input);
+    if crate::display::vertical() {
+        println!("DATA      : shape: {:?}", input.shape());
+        print!("{}", crate::display::records(input));
+    } else {
+        println!("DATA      : {:?}", &input);
+    }
 }
 
+// What #[print_source(show_result)] prints after the example has run,
+// with the same display options as print_data
+pub trait PrintResult {
+    fn print_result(&self);
+}
 
-// utility function
-// Need to enable the "blocking" feature for the reqwest crate
-use reqwest::blocking::Client;
-use std::fs::File;
-use std::io::copy;
-use std::path::Path;
+impl PrintResult for DataFrame {
+    fn print_result(&self) {
+        print_data(self);
+    }
+}
 
-pub fn download_file(url: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    if Path::new(output_path).exists() {
-        println!("File already exists at: {:?}", output_path);
-        return Ok(());
+impl PrintResult for Series {
+    fn print_result(&self) {
+        if crate::display::vertical() {
+            print_data(&self.clone().into_frame());
+        } else {
+            print_type(self);
+            println!("DATA      : {:?}", self);
+        }
     }
+}
 
-    // Create a new blocking client
-    // Send a GET request to the URL
-    // Create the output file
-    // Copy the response body to the output file
-    let client = Client::new()
-        .get(url)
-        .send()?
-        .copy_to(&mut File::create(output_path)?)?;
-
-    println!("File downloaded successfully to: {:?}", output_path);
-    Ok(())
+impl PrintResult for () {
+    fn print_result(&self) {}
 }
 
-// Detect the number of logical CPUs available on the system
-pub fn check_threads_n() {
-    match thread::available_parallelism() {
-        Ok(num) => println!("Number of logical CPUs: {}", num),
-        Err(e) => println!("Couldn't get number of CPUs: {}", e),
+// The error is part of the lesson for examples expected to fail
+impl<T: PrintResult, E: std::fmt::Display> PrintResult for Result<T, E> {
+    fn print_result(&self) {
+        match self {
+            Ok(out) => out.print_result(),
+            Err(e) => println!("ERROR     : {}", e),
+        }
     }
 }
+
+// Run f with stdout silenced, eg for examples that only produce inputs for others.
+// Falls back to printing if stdout cannot be redirected (or is already silenced).
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    stdout().flush().ok();
+    let _gag = gag::Gag::stdout().ok();
+    let out = f();
+    stdout().flush().ok();
+    out
+}
+
+
+// Downloads live in their own module, re-exported for the examples
+pub use crate::download::{download_file, DownloadError};
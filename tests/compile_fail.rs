@@ -0,0 +1,93 @@
+// The attribute macros must reject what they cannot handle with an error at the right place.
+// Each file of tests/compile_fail is compiled with rustc against the macro crate built for
+// this test, and fails with the errors its `//~ ERROR message` comments mark, on their lines.
+// A marked message may leave out the end of the reported one.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// The macro crate next to this test executable, the newest build if there are several
+fn macro_crate(deps: &Path) -> PathBuf {
+    let prefix = format!("{}my_proc_macro-", env::consts::DLL_PREFIX);
+    fs::read_dir(deps)
+        .expect("read the deps directory")
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+            name.starts_with(&prefix) && name.ends_with(env::consts::DLL_SUFFIX)
+        })
+        .max_by_key(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .expect("my_proc_macro is built next to the test executable")
+}
+
+// (line, message) of each `//~ ERROR message` comment
+fn expected_errors(source: &str) -> Vec<(usize, String)> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| line.split_once("//~ ERROR ").map(|(_, message)| (i + 1, message.trim().to_string())))
+        .collect()
+}
+
+// (line, message) of each error rustc reported, from its short format:
+// `file:line:col: error: message` or `file:line:col: error[E0599]: message: label`
+fn reported_errors(stderr: &str, file: &Path) -> Vec<(usize, String)> {
+    let prefix = format!("{}:", file.display());
+    stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix(&prefix))
+        .filter_map(|rest| {
+            let (line, rest) = rest.split_once(':')?;
+            let (_, rest) = rest.split_once(": error")?;
+            let message = rest.strip_prefix(": ").or_else(|| rest.split_once("]: ").map(|(_, message)| message))?;
+            Some((line.parse().ok()?, message.trim().to_string()))
+        })
+        .collect()
+}
+
+// The marked message starts the reported one, which may go on with the label of the span
+fn matches(expected: &[(usize, String)], reported: &[(usize, String)]) -> bool {
+    expected.len() == reported.len()
+        && expected
+            .iter()
+            .zip(reported)
+            .all(|((expected_line, expected), (line, message))| expected_line == line && message.starts_with(expected.as_str()))
+}
+
+#[test]
+fn compile_fail() {
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let macros = macro_crate(&deps);
+    let out_dir = env::temp_dir().join("polars-rust-examples-compile-fail");
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("compile_fail");
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+    files.sort();
+    assert!(!files.is_empty(), "no files in {}", dir.display());
+
+    let mut failures = Vec::new();
+    for file in &files {
+        let output = Command::new(env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+            .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata", "--error-format", "short"])
+            .arg("--extern")
+            .arg(format!("my_proc_macro={}", macros.display()))
+            .arg("-L")
+            .arg(format!("dependency={}", deps.display()))
+            .arg("--out-dir")
+            .arg(&out_dir)
+            .arg(file)
+            .output()
+            .expect("run rustc");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let expected = expected_errors(&fs::read_to_string(file).unwrap());
+        let reported = reported_errors(&stderr, file);
+        if output.status.success() || expected.is_empty() || !matches(&expected, &reported) {
+            failures.push(format!("{}\nexpected: {:?}\nreported: {:?}\n{}", file.display(), expected, reported, stderr));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
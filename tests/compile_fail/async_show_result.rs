@@ -0,0 +1,6 @@
+use my_proc_macro::print_source;
+
+#[print_source(show_result)]
+pub async fn fetch() -> Result<i32, String> { //~ ERROR show_result and notes are not supported on async functions
+    Ok(1)
+}
@@ -0,0 +1,6 @@
+use my_proc_macro::print_source;
+
+#[print_source]
+pub const fn answer() -> i32 { //~ ERROR print_source cannot print from a const fn
+    42
+}
@@ -0,0 +1,5 @@
+use my_proc_macro::{example, print_source};
+
+#[print_source]
+#[example(chapter = 1, id = 1, title = "Registered without its source")] //~ ERROR put #[example] before #[print_source], so that it registers the source
+pub fn ex001() {}
@@ -0,0 +1,4 @@
+use my_proc_macro::example;
+
+#[example(chapter = 1, id = 2, title = "Copied from ex001")] //~ ERROR id = 2 is the example ex002, but the function is named ex001
+pub fn ex001() {}
@@ -0,0 +1,31 @@
+// #[explain] renames every .collect() of the body, it cannot tell the receivers apart.
+// A collect that is neither LazyFrame::collect nor Iterator::collect no longer resolves,
+// here a method of the example's own type. Fully qualified calls are left alone.
+use my_proc_macro::explain;
+
+// Stands in for the module of the binary that the attribute imports
+mod explain {
+    pub trait ExplainLazyFrame {}
+    pub trait ExplainIterator: Iterator + Sized {
+        fn explained_collect<B: FromIterator<Self::Item>>(self) -> B {
+            self.collect()
+        }
+    }
+    impl<I: Iterator> ExplainIterator for I {}
+}
+
+pub struct Bag(Vec<i32>);
+
+impl Bag {
+    pub fn collect(self) -> Vec<i32> {
+        self.0
+    }
+}
+
+#[explain]
+pub fn totals(bag: Bag) -> (Vec<i32>, Vec<i32>) {
+    let doubled: Vec<i32> = [1, 2].into_iter().map(|n| n * 2).collect();
+    let bag = Bag::collect(bag);
+    let other = Bag(bag).collect(); //~ ERROR `Bag` is not an iterator
+    (doubled, other)
+}
@@ -0,0 +1,6 @@
+use my_proc_macro::print_source;
+
+#[print_source(notes = "Evens below ten")]
+pub fn evens() -> impl Iterator<Item = u32> { //~ ERROR show_result and notes need a return type that can be named, not impl Trait
+    (0..10).filter(|n| n % 2 == 0)
+}
@@ -0,0 +1,6 @@
+use my_proc_macro::print_source;
+
+#[print_source(colour = true)] //~ ERROR unsupported print_source argument, expected title, url, notes or show_result
+pub fn answer() -> i32 {
+    42
+}
@@ -0,0 +1,110 @@
+// #[print_source] on the function shapes it accepts. Compiling is most of the test: the
+// attribute must keep receivers, generics and argument patterns, and add no item next to
+// the function, which a trait impl would reject.
+
+use my_proc_macro::print_source;
+
+// Stand in for the modules of the binary that the generated code calls
+mod highlight {
+    pub fn rust(source: &str) -> String {
+        source.to_string()
+    }
+}
+
+mod utilities {
+    pub fn printing() -> bool {
+        true
+    }
+
+    pub trait PrintResult {
+        fn print_result(&self);
+    }
+
+    impl<T: std::fmt::Debug> PrintResult for T {
+        fn print_result(&self) {
+            println!("{:?}", self);
+        }
+    }
+}
+
+const NOTES: &str = "Printed after the function has run";
+
+trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64) -> Result<f64, String>;
+    fn unit() -> Self;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    #[print_source]
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    #[print_source(title = "Scale in place", notes = NOTES, show_result)]
+    fn scale(&mut self, factor: f64) -> Result<f64, String> {
+        if factor <= 0.0 {
+            return Err(format!("cannot scale by {}", factor));
+        }
+        self.0 *= factor;
+        Ok(self.0)
+    }
+
+    #[print_source(url = "https://doc.rust-lang.org/book/ch10-02-traits.html")]
+    fn unit() -> Self {
+        Square(1.0)
+    }
+}
+
+impl Square {
+    #[print_source(notes = NOTES)]
+    pub fn into_side(self) -> f64 {
+        self.0
+    }
+}
+
+#[print_source(title = "Largest item", show_result)]
+fn largest<'a, T>(items: &'a [T]) -> Option<&'a T>
+where
+    T: PartialOrd + std::fmt::Debug,
+{
+    let mut largest = items.first()?;
+    for item in items {
+        if item > largest {
+            largest = item;
+        }
+    }
+    Some(largest)
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[print_source(notes = NOTES)]
+fn sum((a, b): (i32, i32), Point { x, y }: Point, [first, ..]: [i32; 3]) -> i32 {
+    a + b + x + y + first
+}
+
+#[test]
+fn trait_methods() {
+    let mut square = Square::unit();
+    assert_eq!(square.scale(3.0), Ok(3.0));
+    assert_eq!(square.scale(0.0), Err("cannot scale by 0".to_string()));
+    assert_eq!(square.area(), 9.0);
+    assert_eq!(square.into_side(), 3.0);
+}
+
+#[test]
+fn generics_with_where_clause() {
+    assert_eq!(largest(&[3, 7, 5]), Some(&7));
+    assert_eq!(largest::<i32>(&[]), None);
+}
+
+#[test]
+fn pattern_arguments() {
+    assert_eq!(sum((1, 2), Point { x: 3, y: 4 }, [5, 6, 7]), 15);
+}